@@ -1,5 +1,5 @@
 use rustlog::{
-    init_from_env, set_level, set_target, Level, Target, banner,
+    init, set_level, set_target, Level, Target, banner,
     set_show_file_line, set_show_thread_id, set_show_time,
     trace, info, warn, error, fatal,
     scope_time, debug, info_group
@@ -13,7 +13,8 @@ fn main() {
     set_show_file_line(true);
     set_show_thread_id(true);
     set_show_time(true);
-    init_from_env();
+    // reads the env, installs the panic hook, and flushes the sink when dropped
+    let _guard = init();
     
     banner!();
 
@@ -0,0 +1,31 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Source of wall-clock time for timestamps and time-based file rotation
+pub trait Clock {
+    /// Nanoseconds since the Unix epoch (negative before 1970)
+    fn now_unix_nanos(&self) -> i128;
+}
+
+/// The system wall clock (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[allow(clippy::cast_possible_wrap)]
+    fn now_unix_nanos(&self) -> i128 {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_nanos() as i128,
+            Err(e) => -(e.duration().as_nanos() as i128),
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn to_system_time(nanos: i128) -> SystemTime {
+    let d = Duration::from_nanos(nanos.unsigned_abs() as u64);
+    if nanos >= 0 {
+        UNIX_EPOCH + d
+    } else {
+        UNIX_EPOCH - d
+    }
+}
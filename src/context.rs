@@ -0,0 +1,131 @@
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+
+thread_local! {
+    static STACK: RefCell<Vec<(&'static str, String)>> = const { RefCell::new(Vec::new()) };
+    static GROUPS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Removes its context field (and any pushed after it) when dropped
+#[must_use = "the field is popped as soon as the guard is dropped"]
+pub struct ContextGuard {
+    depth: usize,
+    // the field lives in this thread's stack
+    _not_send: PhantomData<*const ()>,
+}
+
+/// Attaches `key=value` to every record logged on this thread until the guard is dropped
+pub fn push_context(key: &'static str, value: impl Into<String>) -> ContextGuard {
+    let value = value.into();
+    let depth = STACK.with(|s| {
+        let mut s = s.borrow_mut();
+        s.push((key, value));
+        s.len() - 1
+    });
+    ContextGuard {
+        depth,
+        _not_send: PhantomData,
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        let _ = STACK.try_with(|s| {
+            if let Ok(mut s) = s.try_borrow_mut() {
+                s.truncate(self.depth);
+            }
+        });
+    }
+}
+
+/// Pops its scope group (and any pushed after it) when dropped
+#[must_use = "the group is popped as soon as the guard is dropped"]
+pub struct GroupGuard {
+    depth: usize,
+    // the group lives in this thread's stack
+    _not_send: PhantomData<*const ()>,
+}
+
+/// Tags records logged on this thread without a group with `group` until the
+/// guard is dropped; the innermost scope wins (see [`scope_group!`](crate::scope_group))
+pub fn push_group(group: &'static str) -> GroupGuard {
+    let depth = GROUPS.with(|s| {
+        let mut s = s.borrow_mut();
+        s.push(group);
+        s.len() - 1
+    });
+    GroupGuard {
+        depth,
+        _not_send: PhantomData,
+    }
+}
+
+impl Drop for GroupGuard {
+    fn drop(&mut self) {
+        let _ = GROUPS.try_with(|s| {
+            if let Ok(mut s) = s.try_borrow_mut() {
+                s.truncate(self.depth);
+            }
+        });
+    }
+}
+
+/// This thread's innermost scope group, if any (any lifetime, so it can stand in
+/// for a borrowed group)
+pub fn current_group<'a>() -> Option<&'a str> {
+    GROUPS
+        .try_with(|s| s.try_borrow().ok().and_then(|s| s.last().copied()))
+        .ok()
+        .flatten()
+}
+
+/// Restores the indentation depth from before it was created when dropped
+/// (also during a panic unwind)
+#[must_use = "the indentation ends as soon as the guard is dropped"]
+pub struct IndentGuard {
+    depth: usize,
+    // the depth is this thread's
+    _not_send: PhantomData<*const ()>,
+}
+
+/// Indents records logged on this thread one level deeper until the guard is
+/// dropped (see [`scope!`](crate::scope) and [`set_indent_width`](crate::set_indent_width))
+pub fn indent_guard() -> IndentGuard {
+    let depth = DEPTH.with(|d| {
+        let depth = d.get();
+        d.set(depth + 1);
+        depth
+    });
+    IndentGuard {
+        depth,
+        _not_send: PhantomData,
+    }
+}
+
+impl Drop for IndentGuard {
+    fn drop(&mut self) {
+        let _ = DEPTH.try_with(|d| d.set(self.depth));
+    }
+}
+
+/// This thread's indentation depth
+pub fn indent_depth() -> usize {
+    DEPTH.try_with(Cell::get).unwrap_or(0)
+}
+
+/// Runs `f` with this thread's active context fields (empty if unavailable)
+pub fn with_context(f: impl FnOnce(&[(&'static str, String)])) {
+    let mut f = Some(f);
+    let _ = STACK.try_with(|s| {
+        let Ok(s) = s.try_borrow() else {
+            return;
+        };
+        if let Some(f) = f.take() {
+            f(&s);
+        }
+    });
+    if let Some(f) = f {
+        f(&[]);
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use crate::Level;
+
+/// Per-group level overrides; the longest `::`-separated prefix wins
+pub struct GroupLevels {
+    // fast path: skip the lock entirely while no override is configured
+    any: AtomicBool,
+    rules: RwLock<Vec<(Box<str>, Level)>>,
+}
+
+impl GroupLevels {
+    pub const fn new() -> Self {
+        Self {
+            any: AtomicBool::new(false),
+            rules: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn set(&self, group: &str, l: Level) {
+        let mut rules = self.rules.write().unwrap();
+        match rules.iter_mut().find(|(g, _)| &**g == group) {
+            Some(rule) => rule.1 = l,
+            None => rules.push((group.into(), l)),
+        }
+        drop(rules);
+        self.any.store(true, Ordering::Relaxed);
+    }
+
+    /// An independent copy of the current rules
+    pub fn snapshot(&self) -> Self {
+        Self {
+            any: AtomicBool::new(self.any.load(Ordering::Relaxed)),
+            rules: RwLock::new(self.rules.read().unwrap().clone()),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.rules.write().unwrap().clear();
+        self.any.store(false, Ordering::Relaxed);
+    }
+
+    /// Level configured for `group`, if any rule matches
+    pub fn level_for(&self, group: &str) -> Option<Level> {
+        if !self.any.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.rules
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(prefix, _)| matches_prefix(group, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|&(_, l)| l)
+    }
+}
+
+/// `net` matches `net` and `net::http`, but not `network`
+fn matches_prefix(group: &str, prefix: &str) -> bool {
+    group
+        .strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Parses `env_logger`-style directives: `info,net=debug,net::http=trace`.
+///
+/// Returns the bare global level (if any) and the per-group rules; invalid entries are skipped.
+pub fn parse_directives(s: &str) -> (Option<Level>, Vec<(&str, Level)>) {
+    let mut global = None;
+    let mut rules = Vec::new();
+    for d in s.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        match d.split_once('=') {
+            Some((g, l)) => {
+                if let Ok(l) = l.trim().parse() {
+                    rules.push((g.trim(), l));
+                }
+            }
+            None => {
+                if let Ok(l) = d.parse() {
+                    global = Some(l);
+                }
+            }
+        }
+    }
+    (global, rules)
+}
@@ -0,0 +1,788 @@
+use core::fmt::{Arguments, Display};
+use std::io::{self, Write};
+use std::thread::ThreadId;
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "color")]
+use crate::color;
+use crate::{
+    write_level, write_timestamp, ColorScope, Eol, GroupColorMode, HumanDuration, Level,
+    LevelStyle, Precision, TimestampFormat,
+};
+#[cfg(feature = "color")]
+use crate::name_rgb;
+
+/// A single log record handed to a [`Format`].
+///
+/// Fields disabled through the `set_show_*` toggles are already resolved to `None`.
+#[non_exhaustive]
+// resolved logger settings, not state flags
+#[allow(clippy::struct_excessive_bools)]
+pub struct Record<'a> {
+    /// Level of the record
+    pub level: Level,
+    /// Group tag, if any
+    pub group: Option<&'a str>,
+    /// Further group tags after `group`, for records logged with several
+    /// (`info_groups!`); empty whenever `group` is `None`
+    pub extra_groups: &'a [&'a str],
+    /// Source file, if file/line output is enabled
+    pub file: Option<&'a str>,
+    /// Source line
+    pub line: u32,
+    /// Module path of the call site, if module output is enabled and it is known
+    pub module: Option<&'a str>,
+    /// Unshortened source path to link `file` to (OSC 8), if hyperlinks are on and
+    /// colors are in use
+    pub file_link: Option<&'a str>,
+    /// The message as plain text, when it was logged from a `&str`
+    /// (`Logger::emit_str`); it is written instead of `args`, which holds the same
+    /// text
+    pub msg: Option<&'a str>,
+    /// The message
+    pub args: &'a Arguments<'a>,
+    /// Time of the record, if timestamps are enabled with [`TimeSource::Wall`](crate::TimeSource)
+    pub timestamp: Option<SystemTime>,
+    /// Monotonic time since startup, if timestamps are enabled with
+    /// [`TimeSource::Uptime`](crate::TimeSource)
+    pub uptime: Option<Duration>,
+    /// Time since the previous line, if delta output is enabled (`None` on the
+    /// first line)
+    pub delta: Option<Duration>,
+    /// Sequence number of the line, if sequence output is enabled
+    pub seq: Option<u64>,
+    /// Machine hostname, if hostname output is enabled and it is known
+    pub hostname: Option<&'a str>,
+    /// Process id, if pid output is enabled
+    pub pid: Option<u32>,
+    /// Emitting thread, if thread ids are enabled
+    pub thread_id: Option<ThreadId>,
+    /// Name to show instead of `thread_id`, if the thread label asks for it and the
+    /// thread is named
+    pub thread_name: Option<&'a str>,
+    /// Whether ANSI colors should be used
+    pub color: bool,
+    /// Thread-local context fields (see `push_context`), outermost first
+    pub context: &'a [(&'static str, String)],
+    /// Structured `key = value` fields, in call order
+    pub fields: &'a [(&'a str, &'a dyn Display)],
+    /// ANSI escape for this level (see `set_level_color`); only meaningful when `color` is set
+    pub level_color: &'static str,
+    /// Whether control characters in the message must be escaped (see `set_sanitize`)
+    pub sanitize: bool,
+    /// Maximum message length in bytes (see `set_max_message_len`)
+    pub max_message_len: Option<usize>,
+    /// What goes between the prefix fields and before the message (see
+    /// `set_field_separator`)
+    pub field_separator: &'a str,
+    /// Opening and closing strings around group tags (see `set_group_delimiters`)
+    pub group_delimiters: (&'a str, &'a str),
+    /// Line ending the record's final `\n` is written as (see `set_eol`)
+    pub eol: Eol,
+    /// How the default format renders the level (see `set_level_style`)
+    pub level_style: LevelStyle,
+    /// How much of the line is colored when `color` is set (see `set_color_scope`)
+    pub color_scope: ColorScope,
+    /// How a colored group tag gets its color (see `set_group_color_mode`)
+    pub group_color_mode: GroupColorMode,
+    /// Whether a colored group tag is also bold (see `set_group_bold`)
+    pub group_bold: bool,
+    /// Whether the timestamp, thread id and `<file:line>` are rendered faint when
+    /// `color` is set (see `set_dim_metadata`)
+    pub dim_metadata: bool,
+    /// Fractional second digits of `timestamp` (see `set_timestamp_precision`)
+    pub timestamp_precision: Precision,
+    /// Whether a UTC `timestamp` ends with `Z` (see `set_timestamp_z_suffix`)
+    pub timestamp_z_suffix: bool,
+    /// Layout of `timestamp` (see `set_timestamp_format`)
+    pub timestamp_format: TimestampFormat,
+    /// Spaces to indent the message by (`scope!` depth times `set_indent_width`)
+    pub indent: usize,
+}
+
+impl Record<'_> {
+    /// Writes the timestamp or uptime (if any) exactly like the default format does
+    /// # Errors
+    /// Returns any error produced by the underlying writer
+    pub fn write_timestamp(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(t) = self.timestamp {
+            write_timestamp(
+                &mut *w,
+                t,
+                self.timestamp_format,
+                self.timestamp_precision,
+                self.timestamp_z_suffix,
+            );
+        }
+        if let Some(d) = self.uptime {
+            write!(w, "[{:4}.{:03}s]", d.as_secs(), d.subsec_millis())?;
+        }
+        Ok(())
+    }
+    /// Writes the context as ` key=value` pairs like the default format does
+    /// # Errors
+    /// Returns any error produced by the underlying writer
+    pub fn write_context(&self, w: &mut dyn Write) -> io::Result<()> {
+        for (k, v) in self.context {
+            write!(w, " {k}=")?;
+            write_logfmt_value(w, v.as_bytes())?;
+        }
+        Ok(())
+    }
+    /// Writes the fields as ` key=value` pairs like the default format does;
+    /// values are quoted and escaped only when needed
+    /// # Errors
+    /// Returns any error produced by the underlying writer
+    pub fn write_fields(&self, w: &mut dyn Write) -> io::Result<()> {
+        let mut val = Vec::new();
+        for (k, v) in self.fields {
+            val.clear();
+            let _ = write!(val, "{v}");
+            write!(w, " {k}=")?;
+            write_logfmt_value(w, &val)?;
+        }
+        Ok(())
+    }
+    /// Writes the message, escaping control characters if `sanitize` is set and
+    /// cutting it at `max_message_len` bytes
+    /// # Errors
+    /// Returns any error produced by the underlying writer
+    pub fn write_message(&self, w: &mut dyn Write) -> io::Result<()> {
+        if self.sanitize {
+            self.write_capped(&mut Sanitize(w))
+        } else {
+            self.write_capped(w)
+        }
+    }
+    /// Writes the message cut at `max_message_len` bytes
+    fn write_capped(&self, w: &mut dyn Write) -> io::Result<()> {
+        match self.max_message_len {
+            None => self.write_args(w),
+            Some(max) => {
+                let mut t = Truncate {
+                    w,
+                    left: max,
+                    cut: false,
+                };
+                self.write_args(&mut t)?;
+                if t.cut {
+                    t.w.write_all("…(truncated)".as_bytes())?;
+                }
+                Ok(())
+            }
+        }
+    }
+    /// A plain-text message (`emit_str`) or one without arguments (`info!("ready")`)
+    /// is written as-is, skipping the `core::fmt` machinery
+    fn write_args(&self, w: &mut dyn Write) -> io::Result<()> {
+        match self.msg.or(self.args.as_str()) {
+            Some(s) => w.write_all(s.as_bytes()),
+            None => w.write_fmt(*self.args),
+        }
+    }
+}
+
+/// Passes through the first `left` bytes, cut on a UTF-8 char boundary, and
+/// swallows the rest
+struct Truncate<'a> {
+    w: &'a mut dyn Write,
+    left: usize,
+    cut: bool,
+}
+
+impl Write for Truncate<'_> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        if self.cut {
+            return Ok(bytes.len());
+        }
+        let mut n = bytes.len().min(self.left);
+        if n < bytes.len() {
+            // `write_fmt` hands over whole `str`s, so backing off continuation
+            // bytes lands on a char boundary
+            while n > 0 && bytes[n] & 0xc0 == 0x80 {
+                n -= 1;
+            }
+            self.cut = true;
+        }
+        self.w.write_all(&bytes[..n])?;
+        self.left -= n;
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Escapes control bytes as `\n`, `\r`, `\t` or `\xNN`, so a message stays on
+/// one line and can't smuggle in ANSI sequences
+struct Sanitize<'a>(&'a mut dyn Write);
+
+impl Write for Sanitize<'_> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        for &b in bytes {
+            match b {
+                b'\n' => self.0.write_all(b"\\n")?,
+                b'\r' => self.0.write_all(b"\\r")?,
+                b'\t' => self.0.write_all(b"\\t")?,
+                0..=0x1f | 0x7f => write!(self.0, "\\x{b:02x}")?,
+                _ => self.0.write_all(&[b])?,
+            }
+        }
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Renders a [`Record`] into a full line, including the trailing newline
+pub trait Format {
+    /// Write the record
+    /// # Errors
+    /// Returns any error produced by the underlying writer
+    fn write(&self, w: &mut dyn Write, record: &Record) -> io::Result<()>;
+}
+
+/// Renders `r` with `f`, falling back to [`DefaultFormat`]
+///
+/// Formats end lines with `\n`; in [`Eol::CrLf`] mode it becomes `\r\n` here.
+pub fn render(buf: &mut Vec<u8>, f: Option<&(dyn Format + Send + Sync)>, r: &Record) {
+    let _ = match f {
+        Some(f) => f.write(buf, r),
+        None => DefaultFormat.write(buf, r),
+    };
+    if r.eol == Eol::CrLf && buf.ends_with(b"\n") && !buf.ends_with(b"\r\n") {
+        buf.insert(buf.len() - 1, b'\r');
+    }
+}
+
+/// [`render`] straight into `w`, without buffering the line
+pub fn render_direct(
+    w: &mut dyn Write,
+    f: Option<&(dyn Format + Send + Sync)>,
+    r: &Record,
+) -> io::Result<()> {
+    let mut w = EolWriter {
+        inner: w,
+        newline: false,
+    };
+    match f {
+        Some(f) => f.write(&mut w, r),
+        None => DefaultFormat.write(&mut w, r),
+    }?;
+    if w.newline {
+        w.inner.write_all(r.eol.as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Holds back a trailing `\n` until more follows, so the line's last one can be
+/// written as the record's [`Eol`]
+struct EolWriter<'a> {
+    inner: &'a mut dyn Write,
+    newline: bool,
+}
+impl Write for EolWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if core::mem::take(&mut self.newline) {
+            self.inner.write_all(b"\n")?;
+        }
+        let body = buf.strip_suffix(b"\n").unwrap_or(buf);
+        self.inner.write_all(body)?;
+        self.newline = body.len() < buf.len();
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Writes the metadata segment `f` writes, in faint styling if `dim_metadata` is
+/// on and the record is colored. The closing reset also ends the line color in
+/// `WholeLine` scope, so that is reopened
+#[cfg_attr(not(feature = "color"), allow(unused_variables))]
+fn dimmed(
+    w: &mut dyn Write,
+    r: &Record,
+    whole_line: bool,
+    f: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> io::Result<()> {
+    #[cfg(feature = "color")]
+    if r.color && r.dim_metadata {
+        w.write_all(color::DIM.as_bytes())?;
+        f(&mut *w)?;
+        w.write_all(color::RST.as_bytes())?;
+        if whole_line {
+            w.write_all(r.level_color.as_bytes())?;
+        }
+        return Ok(());
+    }
+    f(w)
+}
+
+/// The default human readable layout:
+/// `#seq timestamp (+delta) LEVEL [host:name] [pid:N] [tid] <file:line> [group] message`
+///
+/// The group brackets can be changed with
+/// [`Logger::set_group_delimiters`](crate::local::Logger::set_group_delimiters).
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DefaultFormat;
+
+impl Format for DefaultFormat {
+    fn write(&self, w: &mut dyn Write, r: &Record) -> io::Result<()> {
+        // In `WholeLine` scope the line opens with the level color and the
+        // level/group tags are written plain, so nothing resets it early
+        let whole_line = r.color && r.color_scope == ColorScope::WholeLine;
+        let tag_color = r.color && !whole_line;
+        let sep = r.field_separator;
+        if whole_line {
+            w.write_all(r.level_color.as_bytes())?;
+        }
+        if let Some(n) = r.seq {
+            write!(w, "#{n:06}{sep}")?;
+        }
+        if r.timestamp.is_some() || r.uptime.is_some() {
+            dimmed(&mut *w, r, whole_line, |w| r.write_timestamp(w))?;
+            w.write_all(sep.as_bytes())?;
+        }
+        if let Some(d) = r.delta {
+            write!(w, "(+{}){sep}", HumanDuration(d))?;
+        }
+        write_level(
+            &mut *w,
+            r.level,
+            r.level_style,
+            tag_color.then_some(r.level_color),
+        );
+        if let Some(host) = r.hostname {
+            write!(w, "{sep}[host:{host}]")?;
+        }
+        if let Some(pid) = r.pid {
+            write!(w, "{sep}[pid:{pid}]")?;
+        }
+        if let Some(name) = r.thread_name {
+            w.write_all(sep.as_bytes())?;
+            dimmed(&mut *w, r, whole_line, |w| write!(w, "[{name}]"))?;
+        } else if let Some(tid) = r.thread_id {
+            w.write_all(sep.as_bytes())?;
+            dimmed(&mut *w, r, whole_line, |w| write!(w, "[{tid:?}]"))?;
+        }
+        if let Some(file) = r.file {
+            w.write_all(sep.as_bytes())?;
+            dimmed(&mut *w, r, whole_line, |w| match r.file_link {
+                Some(path) => {
+                    w.write_all(b"<")?;
+                    write_file_link(&mut *w, path, r.line)?;
+                    write!(w, "{file}:{}\x1b]8;;\x1b\\>", r.line)
+                }
+                None => write!(w, "<{file}:{}>", r.line),
+            })?;
+        }
+        if let Some(m) = r.module {
+            write!(w, "{sep}{{{m}}}")?;
+        }
+        let (open, close) = r.group_delimiters;
+        for (i, g) in r.group.iter().chain(r.extra_groups).enumerate() {
+            // further tags follow the first directly: `[net][retry]`
+            let sep = if i == 0 { sep } else { "" };
+            #[cfg(feature = "color")]
+            let bold = if r.group_bold { color::BOLD } else { "" };
+            #[cfg(feature = "color")]
+            if tag_color && r.group_color_mode == GroupColorMode::ByName {
+                let (red, green, blue) = name_rgb(g);
+                write!(
+                    w,
+                    "{sep}{open}{bold}\x1b[38;2;{red};{green};{blue}m{g}{}{close}",
+                    color::RST
+                )?;
+            } else if tag_color {
+                write!(
+                    w,
+                    "{sep}{open}{}{}{}{}{close}",
+                    bold,
+                    r.level_color,
+                    g,
+                    color::RST
+                )?;
+            } else {
+                write!(w, "{sep}{open}{g}{close}")?;
+            }
+            #[cfg(not(feature = "color"))]
+            write!(w, "{sep}{open}{g}{close}")?;
+        }
+        if r.indent > 0 {
+            write!(w, "{:1$}", "", r.indent)?;
+        }
+        r.write_context(&mut *w)?;
+        w.write_all(sep.as_bytes())?;
+        r.write_message(&mut *w)?;
+        r.write_fields(&mut *w)?;
+        #[cfg(feature = "color")]
+        if whole_line {
+            w.write_all(color::RST.as_bytes())?;
+        }
+        w.write_all(b"\n")
+    }
+}
+
+/// Opens an OSC 8 hyperlink to `path` (made absolute against the working directory)
+fn write_file_link(w: &mut dyn Write, path: &str, line: u32) -> io::Result<()> {
+    let path = std::path::Path::new(path);
+    let abs = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().map_or_else(|_| path.to_path_buf(), |d| d.join(path))
+    };
+    let abs = abs.to_string_lossy();
+    let sep = if abs.starts_with('/') { "" } else { "/" };
+    write!(w, "\x1b]8;;file://{sep}{}#L{line}\x1b\\", abs.replace('\\', "/"))
+}
+
+/// One compact JSON object per line with keys `ts`, `level`, `host`, `pid`,
+/// `tid`, `group`, `file`, `line` and `msg`; context fields come before `msg`
+/// and record fields after it, all as string values
+#[cfg(feature = "json")]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct JsonFormat;
+
+#[cfg(feature = "json")]
+impl Format for JsonFormat {
+    fn write(&self, w: &mut dyn Write, r: &Record) -> io::Result<()> {
+        w.write_all(b"{")?;
+        if let Some(n) = r.seq {
+            write!(w, "\"seq\":{n},")?;
+        }
+        if let Some(t) = r.timestamp {
+            w.write_all(b"\"ts\":\"")?;
+            write_timestamp(
+                &mut *w,
+                t,
+                r.timestamp_format,
+                r.timestamp_precision,
+                r.timestamp_z_suffix,
+            );
+            w.write_all(b"\",")?;
+        }
+        if let Some(d) = r.uptime {
+            write!(w, "\"uptime\":\"{}.{:03}s\",", d.as_secs(), d.subsec_millis())?;
+        }
+        write!(w, "\"level\":\"{}\"", r.level)?;
+        if let Some(d) = r.delta {
+            write!(w, ",\"delta_ns\":{}", d.as_nanos())?;
+        }
+        if let Some(host) = r.hostname {
+            w.write_all(b",\"host\":\"")?;
+            JsonEscape(&mut *w).write_all(host.as_bytes())?;
+            w.write_all(b"\"")?;
+        }
+        if let Some(pid) = r.pid {
+            write!(w, ",\"pid\":{pid}")?;
+        }
+        if let Some(name) = r.thread_name {
+            w.write_all(b",\"tid\":\"")?;
+            JsonEscape(&mut *w).write_all(name.as_bytes())?;
+            w.write_all(b"\"")?;
+        } else if let Some(tid) = r.thread_id {
+            write!(w, ",\"tid\":\"{tid:?}\"")?;
+        }
+        if let Some(g) = r.group {
+            w.write_all(b",\"group\":\"")?;
+            JsonEscape(&mut *w).write_all(g.as_bytes())?;
+            for g in r.extra_groups {
+                w.write_all(b",")?;
+                JsonEscape(&mut *w).write_all(g.as_bytes())?;
+            }
+            w.write_all(b"\"")?;
+        }
+        if let Some(file) = r.file {
+            w.write_all(b",\"file\":\"")?;
+            JsonEscape(&mut *w).write_all(file.as_bytes())?;
+            write!(w, "\",\"line\":{}", r.line)?;
+        }
+        if let Some(m) = r.module {
+            w.write_all(b",\"module\":\"")?;
+            JsonEscape(&mut *w).write_all(m.as_bytes())?;
+            w.write_all(b"\"")?;
+        }
+        for (k, v) in r.context {
+            w.write_all(b",\"")?;
+            JsonEscape(&mut *w).write_all(k.as_bytes())?;
+            w.write_all(b"\":\"")?;
+            JsonEscape(&mut *w).write_all(v.as_bytes())?;
+            w.write_all(b"\"")?;
+        }
+        w.write_all(b",\"msg\":\"")?;
+        r.write_capped(&mut JsonEscape(&mut *w))?;
+        w.write_all(b"\"")?;
+        for (k, v) in r.fields {
+            w.write_all(b",\"")?;
+            JsonEscape(&mut *w).write_all(k.as_bytes())?;
+            w.write_all(b"\":\"")?;
+            write!(JsonEscape(&mut *w), "{v}")?;
+            w.write_all(b"\"")?;
+        }
+        w.write_all(b"}\n")
+    }
+}
+
+/// Escapes bytes into a JSON string body as they are written
+#[cfg(feature = "json")]
+struct JsonEscape<'a>(&'a mut dyn Write);
+
+#[cfg(feature = "json")]
+impl Write for JsonEscape<'_> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        for &b in bytes {
+            match b {
+                b'"' => self.0.write_all(b"\\\"")?,
+                b'\\' => self.0.write_all(b"\\\\")?,
+                b'\n' => self.0.write_all(b"\\n")?,
+                b'\r' => self.0.write_all(b"\\r")?,
+                b'\t' => self.0.write_all(b"\\t")?,
+                0..=0x1f | 0x7f => write!(self.0, "\\u{b:04x}")?,
+                _ => self.0.write_all(&[b])?,
+            }
+        }
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// `key=value` pairs (logfmt); values are quoted and escaped only when needed
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LogfmtFormat;
+
+impl Format for LogfmtFormat {
+    fn write(&self, w: &mut dyn Write, r: &Record) -> io::Result<()> {
+        let mut val = Vec::new();
+        if let Some(n) = r.seq {
+            write!(w, "seq={n} ")?;
+        }
+        if let Some(t) = r.timestamp {
+            w.write_all(b"ts=")?;
+            write_timestamp(
+                &mut val,
+                t,
+                r.timestamp_format,
+                r.timestamp_precision,
+                r.timestamp_z_suffix,
+            );
+            write_logfmt_value(w, &val)?;
+            w.write_all(b" ")?;
+        }
+        if let Some(d) = r.uptime {
+            write!(w, "uptime={}.{:03}s ", d.as_secs(), d.subsec_millis())?;
+        }
+        write!(w, "level={}", r.level)?;
+        if let Some(d) = r.delta {
+            write!(w, " delta_ns={}", d.as_nanos())?;
+        }
+        if let Some(host) = r.hostname {
+            w.write_all(b" host=")?;
+            write_logfmt_value(w, host.as_bytes())?;
+        }
+        if let Some(pid) = r.pid {
+            write!(w, " pid={pid}")?;
+        }
+        if let Some(name) = r.thread_name {
+            w.write_all(b" tid=")?;
+            write_logfmt_value(w, name.as_bytes())?;
+        } else if let Some(tid) = r.thread_id {
+            write!(w, " tid={tid:?}")?;
+        }
+        if let Some(file) = r.file {
+            val.clear();
+            let _ = write!(val, "{file}:{}", r.line);
+            w.write_all(b" file=")?;
+            write_logfmt_value(w, &val)?;
+        }
+        if let Some(m) = r.module {
+            w.write_all(b" module=")?;
+            write_logfmt_value(w, m.as_bytes())?;
+        }
+        if let Some(g) = r.group {
+            w.write_all(b" group=")?;
+            val.clear();
+            val.extend_from_slice(g.as_bytes());
+            for g in r.extra_groups {
+                val.push(b',');
+                val.extend_from_slice(g.as_bytes());
+            }
+            write_logfmt_value(w, &val)?;
+        }
+        r.write_context(w)?;
+        val.clear();
+        let _ = r.write_capped(&mut val);
+        w.write_all(b" msg=")?;
+        write_logfmt_value(w, &val)?;
+        r.write_fields(w)?;
+        w.write_all(b"\n")
+    }
+}
+
+fn write_logfmt_value(w: &mut dyn Write, v: &[u8]) -> io::Result<()> {
+    let quote = v.is_empty()
+        || v
+            .iter()
+            .any(|&b| matches!(b, b' ' | b'=' | b'"' | b'\\' | 0..=0x1f | 0x7f));
+    if !quote {
+        return w.write_all(v);
+    }
+    w.write_all(b"\"")?;
+    for &b in v {
+        match b {
+            b'"' => w.write_all(b"\\\"")?,
+            b'\\' => w.write_all(b"\\\\")?,
+            b'\n' => w.write_all(b"\\n")?,
+            b'\r' => w.write_all(b"\\r")?,
+            b'\t' => w.write_all(b"\\t")?,
+            0..=0x1f | 0x7f => write!(w, "\\x{b:02x}")?,
+            _ => w.write_all(&[b])?,
+        }
+    }
+    w.write_all(b"\"")
+}
+
+/// Syslog facility (RFC 5424 §6.2.1)
+#[cfg(feature = "syslog")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u8)]
+pub enum Facility {
+    /// Kernel messages
+    Kern = 0,
+    /// User-level messages
+    User = 1,
+    /// Mail system
+    Mail = 2,
+    /// System daemons
+    Daemon = 3,
+    /// Security/authorization
+    Auth = 4,
+    /// Syslogd internal
+    Syslog = 5,
+    /// Line printer
+    Lpr = 6,
+    /// Network news
+    News = 7,
+    /// UUCP
+    Uucp = 8,
+    /// Clock daemon
+    Cron = 9,
+    /// Private security/authorization
+    Authpriv = 10,
+    /// FTP daemon
+    Ftp = 11,
+    /// Local use 0
+    Local0 = 16,
+    /// Local use 1
+    Local1 = 17,
+    /// Local use 2
+    Local2 = 18,
+    /// Local use 3
+    Local3 = 19,
+    /// Local use 4
+    Local4 = 20,
+    /// Local use 5
+    Local5 = 21,
+    /// Local use 6
+    Local6 = 22,
+    /// Local use 7
+    Local7 = 23,
+}
+
+/// RFC 5424 framing: `<PRI>1 TIMESTAMP HOST APP PROCID MSGID - MSG`.
+///
+/// The group is used as `MSGID`; `MSG` is the message followed by context and
+/// fields as ` key=value`. The timestamp is always present (UTC), even when
+/// timestamps are switched off for other formats.
+#[cfg(feature = "syslog")]
+#[derive(Clone, Debug)]
+pub struct SyslogFormat {
+    facility: Facility,
+    hostname: String,
+    app: String,
+}
+
+#[cfg(feature = "syslog")]
+impl SyslogFormat {
+    /// Create a format for `app`; the hostname is looked up like
+    /// [`set_show_hostname`](crate::set_show_hostname) does (`-` if unknown)
+    #[must_use]
+    pub fn new(facility: Facility, app: &str) -> Self {
+        Self {
+            facility,
+            hostname: syslog_token(crate::hostname(), 255),
+            app: syslog_token(app, 48),
+        }
+    }
+    /// Set the hostname sent with every message
+    #[must_use]
+    pub fn with_hostname(mut self, hostname: &str) -> Self {
+        self.hostname = syslog_token(hostname, 255);
+        self
+    }
+    /// Severity (RFC 5424 §6.2.1) for a level; `Trace` and `Debug` share `debug`
+    #[must_use]
+    pub const fn severity(l: Level) -> u8 {
+        match l {
+            Level::Trace | Level::Debug => 7,
+            Level::Info => 6,
+            Level::Notice => 5,
+            Level::Warn => 4,
+            Level::Error => 3,
+            Level::Fatal => 2,
+        }
+    }
+}
+
+/// Header fields are printable ASCII without spaces, `-` when empty
+#[cfg(feature = "syslog")]
+fn syslog_token(s: &str, max: usize) -> String {
+    let t: String = s.chars().filter(char::is_ascii_graphic).take(max).collect();
+    if t.is_empty() {
+        "-".into()
+    } else {
+        t
+    }
+}
+
+#[cfg(feature = "syslog")]
+impl Format for SyslogFormat {
+    fn write(&self, w: &mut dyn Write, r: &Record) -> io::Result<()> {
+        let pri = (self.facility as u8) * 8 + Self::severity(r.level);
+        write!(w, "<{pri}>1 ")?;
+        let ts = r.timestamp.unwrap_or_else(crate::now);
+        let ts = ts
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        #[allow(clippy::cast_possible_wrap)]
+        let secs = ts.as_secs() as i64;
+        let (year, month, day) = crate::civil_from_days_utc(secs.div_euclid(86_400));
+        let sod = secs.rem_euclid(86_400);
+        write!(
+            w,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+            sod / 3_600,
+            sod % 3_600 / 60,
+            sod % 60,
+            ts.subsec_millis()
+        )?;
+        let msgid = r.group.map_or_else(|| "-".into(), |g| syslog_token(g, 32));
+        write!(
+            w,
+            " {} {} {} {msgid} - ",
+            self.hostname,
+            self.app,
+            std::process::id()
+        )?;
+        r.write_message(&mut *w)?;
+        r.write_context(&mut *w)?;
+        r.write_fields(&mut *w)?;
+        w.write_all(b"\n")
+    }
+}
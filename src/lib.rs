@@ -1,15 +1,49 @@
-#![forbid(missing_docs, unsafe_code)]
+#![forbid(missing_docs)]
+// The only unsafe code is the Windows console call in `win_vt`
+#![cfg_attr(not(all(windows, feature = "color")), forbid(unsafe_code))]
+#![cfg_attr(all(windows, feature = "color"), deny(unsafe_code))]
 //! A minimal logging crate.
 
 use core::fmt::Arguments;
-use std::io::{self, IsTerminal, Write};
+#[cfg(feature = "color")]
+use std::io::IsTerminal;
+use std::io::{self, Write};
 use std::path::Path;
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
-use std::sync::{Mutex as StdMutex, OnceLock};
-use std::time::Instant;
+#[cfg(feature = "color")]
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, RwLock};
+use std::time::{Instant, SystemTime};
+
+use local::Logger;
 
 /// Local logger
 pub mod local;
+/// Output sinks
+pub mod sink;
+
+mod clock;
+mod context;
+mod filter;
+mod format;
+mod sync;
+#[cfg(feature = "log-compat")]
+mod log_bridge;
+#[cfg(feature = "tracing-compat")]
+mod tracing_layer;
+#[cfg(all(windows, feature = "color"))]
+mod win_vt;
+pub use clock::{Clock, SystemClock};
+pub use context::{indent_guard, push_context, push_group, ContextGuard, GroupGuard, IndentGuard};
+#[cfg(feature = "json")]
+pub use format::JsonFormat;
+pub use format::{DefaultFormat, Format, LogfmtFormat, Record};
+#[cfg(feature = "syslog")]
+pub use format::{Facility, SyslogFormat};
+#[cfg(feature = "log-compat")]
+pub use log_bridge::{init_log_bridge, LogBridge};
+#[cfg(feature = "tracing-compat")]
+pub use tracing_layer::{tracing_layer, TracingLayer};
 
 /// Log levels
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -21,6 +55,8 @@ pub enum Level {
     Debug,
     /// Info
     Info,
+    /// Notice: normal but significant (between `Info` and `Warn`)
+    Notice,
     /// Warn
     Warn,
     /// Error
@@ -29,17 +65,89 @@ pub enum Level {
     Fatal,
 }
 
-// In debug builds, include all levels (Trace+).
-// In release builds, compile out TRACE/DEBUG entirely for zero overhead.
-#[cfg(debug_assertions)]
-const CT_MIN: Level = Level::Trace;
-#[cfg(not(debug_assertions))]
-const CT_MIN: Level = Level::Info;
-static RUNTIME_LEVEL: AtomicU8 = AtomicU8::new(Level::Info as u8);
-static SHOW_TID: AtomicBool = AtomicBool::new(cfg!(feature = "thread-id"));
-static SHOW_TIME: AtomicBool = AtomicBool::new(cfg!(feature = "timestamp"));
-static SHOW_GROUP: AtomicBool = AtomicBool::new(true);
-static SHOW_FILE_LINE: AtomicBool = AtomicBool::new(cfg!(feature = "file-line"));
+impl Level {
+    /// Returns the uppercase level name
+    #[inline]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Trace => "TRACE",
+            Self::Debug => "DEBUG",
+            Self::Info => "INFO",
+            Self::Notice => "NOTICE",
+            Self::Warn => "WARN",
+            Self::Error => "ERROR",
+            Self::Fatal => "FATAL",
+        }
+    }
+    /// Returns every level in ascending order
+    #[inline]
+    #[must_use]
+    pub const fn all() -> [Self; 7] {
+        [
+            Self::Trace,
+            Self::Debug,
+            Self::Info,
+            Self::Notice,
+            Self::Warn,
+            Self::Error,
+            Self::Fatal,
+        ]
+    }
+}
+
+// Lowest level compiled in (`None` for none). Precedence, first match wins:
+// 1. in release builds, a `release_max_level_*` feature (most restrictive first)
+// 2. a `max_level_*` feature, in any build (most restrictive first)
+// 3. the profile: debug builds include all levels, release builds compile out
+//    TRACE/DEBUG entirely for zero overhead
+const CT_MIN: Option<Level> = if cfg!(debug_assertions) {
+    MAX_LEVEL
+} else if cfg!(feature = "release_max_level_off") {
+    None
+} else if cfg!(feature = "release_max_level_error") {
+    Some(Level::Error)
+} else if cfg!(feature = "release_max_level_warn") {
+    Some(Level::Warn)
+} else if cfg!(feature = "release_max_level_info") {
+    Some(Level::Info)
+} else if cfg!(feature = "release_max_level_debug") {
+    Some(Level::Debug)
+} else if cfg!(feature = "release_max_level_trace") {
+    Some(Level::Trace)
+} else {
+    MAX_LEVEL
+};
+const MAX_LEVEL: Option<Level> = if cfg!(feature = "max_level_off") {
+    None
+} else if cfg!(feature = "max_level_error") {
+    Some(Level::Error)
+} else if cfg!(feature = "max_level_warn") {
+    Some(Level::Warn)
+} else if cfg!(feature = "max_level_info") {
+    Some(Level::Info)
+} else if cfg!(feature = "max_level_debug") {
+    Some(Level::Debug)
+} else if cfg!(any(feature = "max_level_trace", debug_assertions)) {
+    Some(Level::Trace)
+} else {
+    Some(Level::Info)
+};
+// The global functions and macros all go through this one
+static GLOBAL: Logger = Logger::new();
+// `None` means `SystemClock`
+static CLOCK: RwLock<Option<Arc<dyn Clock + Send + Sync>>> = RwLock::new(None);
+
+/// Returns the global logger, for passing where a `&Logger` is expected.
+///
+/// Configuring it is the same as calling the free functions (`global().set_level(l)`
+/// is `set_level(l)`), and the local macros given `global()` log exactly like the
+/// root ones.
+#[inline]
+#[must_use]
+pub fn global() -> &'static Logger {
+    &GLOBAL
+}
 
 /// Color mode
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -52,29 +160,32 @@ pub enum ColorMode {
     /// Never
     Never,
 }
-static COLOR_MODE: AtomicU8 = AtomicU8::new(ColorMode::Auto as u8);
 #[inline]
+// `2` is spelled out even though the default arm covers it
+#[allow(clippy::match_same_arms)]
 const fn level_from_u8(x: u8) -> Level {
     match x {
         0 => Level::Trace,
         1 => Level::Debug,
-        3 => Level::Warn,
-        4 => Level::Error,
-        5 => Level::Fatal,
+        2 => Level::Info,
+        3 => Level::Notice,
+        4 => Level::Warn,
+        5 => Level::Error,
+        6 => Level::Fatal,
         _ => Level::Info, // sane default
     }
 }
+/// Returns the current color mode
 #[inline]
-const fn color_mode_from_u8(x: u8) -> ColorMode {
-    match x {
-        1 => ColorMode::Always,
-        2 => ColorMode::Never,
-        _ => ColorMode::Auto,
-    }
+#[must_use]
+pub fn color_mode() -> ColorMode {
+    GLOBAL.color_mode()
 }
+/// Returns the lowest level that gets colored
 #[inline]
-fn color_mode() -> ColorMode {
-    color_mode_from_u8(COLOR_MODE.load(Ordering::Relaxed))
+#[must_use]
+pub fn color_min_level() -> Level {
+    GLOBAL.color_min_level()
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -103,8 +214,353 @@ impl core::convert::TryFrom<&str> for ColorMode {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// parsing level error
+pub struct ParseLevelError;
+
+/// Accepts the level names in any case, plus `note`, the label text lines use for
+/// `Notice`
+impl core::str::FromStr for Level {
+    type Err = ParseLevelError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("trace") {
+            Ok(Self::Trace)
+        } else if s.eq_ignore_ascii_case("debug") {
+            Ok(Self::Debug)
+        } else if s.eq_ignore_ascii_case("info") {
+            Ok(Self::Info)
+        } else if s.eq_ignore_ascii_case("notice") || s.eq_ignore_ascii_case("note") {
+            Ok(Self::Notice)
+        } else if s.eq_ignore_ascii_case("warn") {
+            Ok(Self::Warn)
+        } else if s.eq_ignore_ascii_case("error") {
+            Ok(Self::Error)
+        } else if s.eq_ignore_ascii_case("fatal") {
+            Ok(Self::Fatal)
+        } else {
+            Err(ParseLevelError)
+        }
+    }
+}
+
+/// Checked inverse of `level as u8`: `0` is `Trace` … `6` is `Fatal`
+impl core::convert::TryFrom<u8> for Level {
+    type Error = ParseLevelError;
+    fn try_from(x: u8) -> Result<Self, ParseLevelError> {
+        if x <= Self::Fatal as u8 {
+            Ok(level_from_u8(x))
+        } else {
+            Err(ParseLevelError)
+        }
+    }
+}
+
+impl core::fmt::Display for Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Notice => "notice",
+            Self::Warn => "warn",
+            Self::Error => "error",
+            Self::Fatal => "fatal",
+        })
+    }
+}
+
+/// How the source path is shown in `<file:line>`
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum PathStyle {
+    /// The path as given by `file!()` (the default)
+    #[default]
+    Full,
+    /// The path with this prefix (and the separator after it) removed
+    Relative(&'static str),
+    /// Only the file name
+    FileName,
+}
+impl PathStyle {
+    /// Applies the style to `file`; paths that don't start with the `Relative`
+    /// prefix are left unchanged
+    #[must_use]
+    pub fn apply(self, file: &str) -> &str {
+        let is_sep = |c| c == '/' || c == '\\';
+        match self {
+            Self::Full => file,
+            Self::Relative(base) => file
+                .strip_prefix(base)
+                .map_or(file, |rest| rest.trim_start_matches(is_sep)),
+            Self::FileName => file.rsplit(is_sep).next().unwrap_or(file),
+        }
+    }
+}
+
+/// Number of fractional second digits in timestamps
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum Precision {
+    /// Whole seconds
+    Secs,
+    /// 3 digits (the default)
+    #[default]
+    Millis,
+    /// 6 digits
+    Micros,
+    /// 9 digits
+    Nanos,
+}
+#[inline]
+pub(crate) const fn precision_from_u8(x: u8) -> Precision {
+    match x {
+        0 => Precision::Secs,
+        2 => Precision::Micros,
+        3 => Precision::Nanos,
+        _ => Precision::Millis,
+    }
+}
+/// Sets the fractional second digits of wall-clock timestamps
+pub fn set_timestamp_precision(p: Precision) {
+    GLOBAL.set_timestamp_precision(p);
+}
+/// Returns the fractional second digits of wall-clock timestamps
+#[inline]
+#[must_use]
+pub fn timestamp_precision() -> Precision {
+    GLOBAL.timestamp_precision()
+}
+/// Sets whether UTC timestamps end with `Z` (the default)
+pub fn set_timestamp_z_suffix(on: bool) {
+    GLOBAL.set_timestamp_z_suffix(on);
+}
+/// Returns whether UTC timestamps end with `Z`
+#[inline]
+#[must_use]
+pub fn timestamp_z_suffix() -> bool {
+    GLOBAL.timestamp_z_suffix()
+}
+
+/// Layout of wall-clock timestamps
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum TimestampFormat {
+    /// `2024-01-15 12:00:00.000Z` (the default)
+    #[default]
+    SpaceSeparated,
+    /// `2024-01-15T12:00:00.000Z`; always carries an offset, so local time (the
+    /// `localtime` feature) or a disabled `Z` suffix renders `+02:00` / `+00:00`
+    Rfc3339,
+}
+/// Sets the layout of wall-clock timestamps
+pub fn set_timestamp_format(f: TimestampFormat) {
+    GLOBAL.set_timestamp_format(f);
+}
+/// Returns the layout of wall-clock timestamps
+#[inline]
+#[must_use]
+pub fn timestamp_format() -> TimestampFormat {
+    GLOBAL.timestamp_format()
+}
+
+/// How the level is rendered by the default format
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum LevelStyle {
+    /// `INFO `, padded to five columns (the default)
+    #[default]
+    Full,
+    /// A single letter: `T` `D` `I` `N` `W` `E` `F`
+    Short,
+    /// `info `, padded to five columns
+    Lower,
+}
+/// Sets how the default format renders levels
+pub fn set_level_style(s: LevelStyle) {
+    GLOBAL.set_level_style(s);
+}
+/// Returns how the default format renders levels
+#[inline]
+#[must_use]
+pub fn level_style() -> LevelStyle {
+    GLOBAL.level_style()
+}
+
+/// How much of a line the default format colors by level
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum ColorScope {
+    /// Only the level token and group tag (the default)
+    #[default]
+    LevelOnly,
+    /// The whole line, from the timestamp to the last field
+    WholeLine,
+}
+/// Sets how much of a line is colored when colors are on
+pub fn set_color_scope(s: ColorScope) {
+    GLOBAL.set_color_scope(s);
+}
+/// Returns how much of a line is colored when colors are on
+#[inline]
+#[must_use]
+pub fn color_scope() -> ColorScope {
+    GLOBAL.color_scope()
+}
+
+/// How the default format colors the `[group]` tag
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum GroupColorMode {
+    /// The record's level color (the default)
+    #[default]
+    ByLevel,
+    /// A 24-bit color derived from the group name, the same for a name on every run
+    ByName,
+}
+/// Sets how the `[group]` tag is colored when colors are on
+pub fn set_group_color_mode(m: GroupColorMode) {
+    GLOBAL.set_group_color_mode(m);
+}
+/// Returns how the `[group]` tag is colored
+#[inline]
+#[must_use]
+pub fn group_color_mode() -> GroupColorMode {
+    GLOBAL.group_color_mode()
+}
+/// Sets whether a colored `[group]` tag is also bold (the default)
+///
+/// Some terminals render bold as a brighter color; turning it off keeps just the color.
+pub fn set_group_bold(on: bool) {
+    GLOBAL.set_group_bold(on);
+}
+/// Returns whether a colored `[group]` tag is bold
+#[inline]
+#[must_use]
+pub fn group_bold() -> bool {
+    GLOBAL.group_bold()
+}
+/// Sets whether the default format renders the timestamp, thread id and
+/// `<file:line>` faint when colors are in use, so the message stands out; off by
+/// default
+pub fn set_dim_metadata(on: bool) {
+    GLOBAL.set_dim_metadata(on);
+}
+/// Returns whether metadata is rendered faint
+#[inline]
+#[must_use]
+pub fn dim_metadata() -> bool {
+    GLOBAL.dim_metadata()
+}
+/// RGB for a group name: FNV-1a (fixed, unlike `DefaultHasher`), with each channel
+/// kept in 80..=255 so it stays readable on dark backgrounds
+#[cfg(feature = "color")]
+fn name_rgb(name: &str) -> (u8, u8, u8) {
+    let mut h: u32 = 0x811c_9dc5;
+    for &b in name.as_bytes() {
+        h = (h ^ u32::from(b)).wrapping_mul(0x0100_0193);
+    }
+    let [r, g, b, _] = h.to_le_bytes();
+    let ch = |x: u8| 80 + x % 176;
+    (ch(r), ch(g), ch(b))
+}
+
+/// Line ending written after each record and banner
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum Eol {
+    /// `\n` (the default)
+    #[default]
+    Lf,
+    /// `\r\n`, for tools that expect Windows line endings
+    CrLf,
+}
+impl Eol {
+    /// The bytes this line ending is written as
+    #[must_use]
+    pub const fn as_bytes(self) -> &'static [u8] {
+        match self {
+            Self::Lf => b"\n",
+            Self::CrLf => b"\r\n",
+        }
+    }
+}
+/// Sets the line ending of records and banners (see [`Logger::set_eol`])
+pub fn set_eol(e: Eol) {
+    GLOBAL.set_eol(e);
+}
+/// Returns the line ending of records and banners
+#[inline]
+#[must_use]
+pub fn eol() -> Eol {
+    GLOBAL.eol()
+}
+
+/// Sets what the default format puts between the prefix fields (timestamp, level,
+/// `[tid]`, `<file:line>`, `[group]`, ...) and before the message; default `" "`
+/// # Panics
+/// This function will panic if locking the separator fails
+pub fn set_field_separator(sep: &'static str) {
+    GLOBAL.set_field_separator(sep);
+}
+/// Returns the separator between prefix fields
+/// # Panics
+/// This function will panic if locking the separator fails
+#[must_use]
+pub fn field_separator() -> &'static str {
+    GLOBAL.field_separator()
+}
+/// Sets the strings the default format wraps the group tag in; default `"["` and
+/// `"]"`
+/// # Panics
+/// This function will panic if locking the delimiters fails
+pub fn set_group_delimiters(open: &'static str, close: &'static str) {
+    GLOBAL.set_group_delimiters(open, close);
+}
+/// Returns the opening and closing group tag delimiters
+/// # Panics
+/// This function will panic if locking the delimiters fails
+#[must_use]
+pub fn group_delimiters() -> (&'static str, &'static str) {
+    GLOBAL.group_delimiters()
+}
+
+/// Sets how many spaces each nested [`scope!`] indents the message by (default 2)
+pub fn set_indent_width(spaces: usize) {
+    GLOBAL.set_indent_width(spaces);
+}
+/// Returns how many spaces each nested [`scope!`] indents the message by
+#[inline]
+#[must_use]
+pub fn indent_width() -> usize {
+    GLOBAL.indent_width()
+}
+
+/// What the timestamp shows
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum TimeSource {
+    /// Wall-clock date and time (the default)
+    #[default]
+    Wall,
+    /// Monotonic seconds since startup, as `[   1.234s]`; "startup" is the first
+    /// time rustlog reads its monotonic clock
+    Uptime,
+}
+
+/// How the thread is labelled in `[tid]`
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+#[repr(u8)]
+pub enum ThreadLabel {
+    /// `ThreadId(3)` (the default)
+    #[default]
+    Id,
+    /// The thread name, or the id for unnamed threads
+    Name,
+    /// The thread name if set, otherwise the id
+    NameOrId,
+}
+
 /// Output target
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Target {
     /// stdout
     Stdout,
@@ -112,59 +568,193 @@ pub enum Target {
     Stderr,
     /// custom
     Writer,
+    /// stderr for records at or above the level, stdout below it
+    Split(Level),
 }
-static TARGET: OnceLock<Target> = OnceLock::new();
-static WRITER: OnceLock<StdMutex<Box<dyn Write + Send>>> = OnceLock::new();
-/// Sets the output target once. Subsequent calls are ignored.
-/// Call this early (e.g., at program start) if you need `Stdout` or a custom `Writer`.
+impl Target {
+    /// The stream a record at `l` goes to (`Split` resolved to `Stdout`/`Stderr`)
+    #[inline]
+    const fn route(self, l: Level) -> Self {
+        match self {
+            Self::Split(min) if (l as u8) >= (min as u8) => Self::Stderr,
+            Self::Split(_) => Self::Stdout,
+            t => t,
+        }
+    }
+}
+/// Sets the output target, replacing any previously selected one.
 pub fn set_target(t: Target) {
-    let _ = TARGET.set(t);
+    GLOBAL.set_target(t);
 }
-/// Sets the output target to a custom writer.
-///
-/// Note: the target is configured only once; call this before emitting any logs.
+/// Sets the output target only if none was selected yet. Subsequent calls are ignored.
+pub fn set_target_once(t: Target) {
+    GLOBAL.set_target_once(t);
+}
+/// Resets the output target to the default (`Stderr`) and drops the custom writer, if any.
+pub fn reset_target() {
+    GLOBAL.reset_target();
+}
+/// Sets the output target to a custom writer, replacing the previous one.
 pub fn set_writer(w: Box<dyn Write + Send>) {
-    let _ = WRITER.set(StdMutex::new(w));
-    // Best-effort: if the target hasn't been selected yet, route output to the writer.
-    let _ = TARGET.set(Target::Writer);
+    GLOBAL.set_writer(w);
 }
-/// Sets the output target to a file.
+/// Installs `w` (selecting `Target::Writer`) and returns the writer it replaces,
+/// if any, so it can be restored afterwards (see [`Logger::replace_writer`])
+pub fn replace_writer(w: Box<dyn Write + Send>) -> Option<Box<dyn Write + Send>> {
+    GLOBAL.replace_writer(w)
+}
+/// Discards all output while still formatting every enabled record (see
+/// [`sink::NullWriter`]); unlike disabling logging, this measures formatting cost.
+pub fn set_null() {
+    set_writer(Box::new(sink::NullWriter));
+}
+/// How [`set_file_with`] (and `LoggerBuilder::file_with`) opens the log file
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct FileOptions {
+    /// Add to the end of an existing file (the default)
+    pub append: bool,
+    /// Create missing parent directories first
+    pub create_dirs: bool,
+    /// Start from an empty file; use with `append: false`
+    pub truncate: bool,
+}
+impl Default for FileOptions {
+    fn default() -> Self {
+        Self {
+            append: true,
+            create_dirs: false,
+            truncate: false,
+        }
+    }
+}
+impl FileOptions {
+    /// Open (creating if needed) `path` for writing with these options
+    /// # Errors
+    /// Returns an error if a directory cannot be created or the file cannot be
+    /// opened, including when both `append` and `truncate` are set.
+    pub fn open(&self, path: impl AsRef<Path>) -> io::Result<std::fs::File> {
+        let path = path.as_ref();
+        if self.create_dirs {
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(self.append)
+            .truncate(self.truncate)
+            .open(path)
+    }
+}
+/// Sets the output target to a file, appending to it.
 /// # Errors
 /// This function will return an error if the file cannot be opened for writing.
 pub fn set_file(path: impl AsRef<Path>) -> io::Result<()> {
-    let f = std::fs::OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(path)?;
-    set_writer(Box::new(f));
-    set_target(Target::Writer);
+    set_file_with(path, FileOptions::default())
+}
+/// Sets the output target to a file opened with `opts`.
+/// # Errors
+/// This function will return an error if the file cannot be opened for writing.
+pub fn set_file_with(path: impl AsRef<Path>, opts: FileOptions) -> io::Result<()> {
+    set_writer(Box::new(opts.open(path)?));
+    Ok(())
+}
+/// Sets the output target to a size-rotated file (see [`sink::RotatingFile`]).
+/// # Errors
+/// This function will return an error if the file cannot be opened for writing.
+pub fn set_rotating_file(path: impl AsRef<Path>, max_bytes: u64, keep: usize) -> io::Result<()> {
+    set_writer(Box::new(sink::RotatingFile::new(path, max_bytes, keep)?));
+    Ok(())
+}
+/// Sets the output target to a file rolled daily at UTC midnight (see [`sink::DailyFile`]).
+/// # Errors
+/// This function will return an error if the file cannot be opened for writing.
+pub fn set_daily_file(dir: impl AsRef<Path>, prefix: &str) -> io::Result<()> {
+    set_writer(Box::new(sink::DailyFile::new(dir, prefix)?));
+    Ok(())
+}
+/// Sets the output target to a gzip-compressed file (see [`sink::GzipFile`]).
+///
+/// Lines only become readable once [`flush`] is called or the sink is replaced.
+/// # Errors
+/// This function will return an error if the file cannot be opened for writing.
+#[cfg(feature = "gzip")]
+pub fn set_gzip_file(path: impl AsRef<Path>) -> io::Result<()> {
+    set_writer(Box::new(sink::GzipFile::new(path)?));
     Ok(())
 }
+/// Sets the output target to `inner` behind a background thread (see
+/// [`sink::AsyncWriter`]); callers block only when `capacity` lines are already queued.
+///
+/// [`flush`] waits for the queue to drain; [`reset_target`] or installing another
+/// writer shuts the thread down after writing what is queued.
+/// # Errors
+/// This function will return an error if the thread cannot be spawned.
+#[cfg(feature = "async-sink")]
+pub fn set_async_writer(inner: Box<dyn Write + Send>, capacity: usize) -> io::Result<()> {
+    set_writer(Box::new(sink::AsyncWriter::new(
+        inner,
+        capacity,
+        sink::Overflow::Block,
+    )?));
+    Ok(())
+}
+/// Sends every record to a syslog collector at `addr` as one RFC 5424 UDP datagram
+/// per line (see [`SyslogFormat`] and [`sink::SyslogWriter`]); this replaces both the
+/// writer and the format.
+/// # Errors
+/// This function will return an error if the UDP socket cannot be set up.
+#[cfg(feature = "syslog")]
+pub fn set_syslog(addr: std::net::SocketAddr, facility: Facility, app: &str) -> io::Result<()> {
+    set_writer(Box::new(sink::SyslogWriter::new(addr)?));
+    set_format(Box::new(SyslogFormat::new(facility, app)));
+    Ok(())
+}
+/// Sends records at `threshold` and above to stderr and the rest to stdout
+/// (shorthand for `set_target(Target::Split(threshold))`).
+pub fn set_split(threshold: Level) {
+    set_target(Target::Split(threshold));
+}
+/// Returns the current output target (`Stderr` unless set otherwise)
 #[inline]
-fn target() -> Target {
-    *TARGET.get_or_init(|| Target::Stderr)
+#[must_use]
+pub fn target() -> Target {
+    GLOBAL.target()
 }
 
-static EMIT_LOCK: StdMutex<()> = StdMutex::new(());
-
 /// Returns `true` if the logger is enabled for the given level
 #[inline]
 #[must_use]
 pub const fn ct_enabled(l: Level) -> bool {
-    (l as u8) >= (CT_MIN as u8)
+    match CT_MIN {
+        Some(min) => (l as u8) >= (min as u8),
+        None => false,
+    }
 }
+/// Returns `true` if a record at `l` (in `group`) would currently be emitted
 #[inline]
-fn rt_enabled(l: Level) -> bool {
-    (l as u8) >= RUNTIME_LEVEL.load(Ordering::Relaxed)
+#[must_use]
+pub fn enabled(l: Level, group: Option<&str>) -> bool {
+    ct_enabled(l) && GLOBAL.enabled(l, group)
+}
+/// Returns `true` if a record at `l` in `group` would currently be emitted, so an
+/// expensive message can be built only when it will be used
+#[inline]
+#[must_use]
+pub fn group_enabled(group: &str, l: Level) -> bool {
+    GLOBAL.group_enabled(group, l)
 }
 
 #[cfg(feature = "color")]
 mod color {
     pub const RST: &str = "\x1b[0m";
     pub const BOLD: &str = "\x1b[1m";
+    pub const DIM: &str = "\x1b[2m";
     pub const TRACE: &str = "\x1b[90m"; // bright black
     pub const DEBUG: &str = "\x1b[36m"; // cyan
     pub const INFO: &str = "\x1b[32m"; // green
+    pub const NOTICE: &str = "\x1b[34m"; // blue
     pub const WARN: &str = "\x1b[33m"; // yellow
     pub const ERROR: &str = "\x1b[31m"; // red
     pub const FATAL: &str = "\x1b[35m"; // magenta
@@ -173,106 +763,783 @@ mod color {
 #[cfg(feature = "color")]
 #[inline]
 const fn level_color(l: Level) -> &'static str {
-    use color::{DEBUG, ERROR, FATAL, INFO, TRACE, WARN};
+    use color::{DEBUG, ERROR, FATAL, INFO, NOTICE, TRACE, WARN};
     match l {
         Level::Trace => TRACE,
         Level::Debug => DEBUG,
         Level::Info => INFO,
+        Level::Notice => NOTICE,
         Level::Warn => WARN,
         Level::Error => ERROR,
         Level::Fatal => FATAL,
     }
 }
 
-/// Returns the uppercase level name
-#[inline]
-const fn level_name(l: Level) -> &'static str {
-    match l {
-        Level::Trace => "TRACE",
-        Level::Debug => "DEBUG",
-        Level::Info => "INFO",
-        Level::Warn => "WARN",
-        Level::Error => "ERROR",
-        Level::Fatal => "FATAL",
+/// Overrides the ANSI escape used for `l`.
+///
+/// The string is written verbatim before the level name, so pass a full escape
+/// such as `"\x1b[38;5;208m"`; it is not validated.
+/// # Panics
+/// This function will panic if locking the color table fails
+pub fn set_level_color(l: Level, ansi: &'static str) {
+    GLOBAL.set_level_color(l, ansi);
+}
+
+/// Windows consoles only render ANSI once virtual terminal processing is enabled
+#[cfg(all(windows, feature = "color"))]
+fn vt_enabled(target: Target) -> bool {
+    match target {
+        Target::Stdout => win_vt::stdout(),
+        Target::Stderr => win_vt::stderr(),
+        Target::Writer => true,
+        Target::Split(_) => win_vt::stdout() && win_vt::stderr(),
     }
 }
+#[cfg(all(not(windows), feature = "color"))]
+#[inline]
+const fn vt_enabled(_target: Target) -> bool {
+    true
+}
 
-fn use_color() -> bool {
-    #[cfg(not(feature = "color"))]
-    {
-        false
+// `NO_COLOR` / `CLICOLOR_FORCE`, read lazily and refreshed by `init_from_env`
+#[cfg(feature = "color")]
+const ENV_COLOR_UNREAD: u8 = 0;
+#[cfg(feature = "color")]
+const ENV_COLOR_NONE: u8 = 1;
+#[cfg(feature = "color")]
+const ENV_COLOR_NO: u8 = 2;
+#[cfg(feature = "color")]
+const ENV_COLOR_FORCE: u8 = 3;
+#[cfg(feature = "color")]
+static ENV_COLOR: AtomicU8 = AtomicU8::new(ENV_COLOR_UNREAD);
+
+#[cfg(feature = "color")]
+fn read_env_color() -> u8 {
+    let var = |k| std::env::var_os(k).filter(|v| !v.is_empty());
+    let v = if var("NO_COLOR").is_some() {
+        ENV_COLOR_NO
+    } else if var("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+        ENV_COLOR_FORCE
+    } else {
+        ENV_COLOR_NONE
+    };
+    ENV_COLOR.store(v, Ordering::Relaxed);
+    v
+}
+
+/// `ColorMode::Auto` decision: `NO_COLOR` disables, `CLICOLOR_FORCE` enables, else TTY detection
+#[cfg(feature = "color")]
+fn auto_color(target: Target) -> bool {
+    let env = match ENV_COLOR.load(Ordering::Relaxed) {
+        ENV_COLOR_UNREAD => read_env_color(),
+        v => v,
+    };
+    match env {
+        ENV_COLOR_NO => false,
+        ENV_COLOR_FORCE => true,
+        _ => match target {
+            Target::Stdout | Target::Stderr => tty_color(target),
+            Target::Writer => false, // unknown sink => assume no TTY
+            Target::Split(_) => tty_color(Target::Stdout) && tty_color(Target::Stderr),
+        },
     }
-    #[cfg(feature = "color")]
-    {
-        match color_mode() {
-            ColorMode::Always => true,
-            ColorMode::Never => false,
-            ColorMode::Auto => match target() {
-                Target::Stdout => io::stdout().is_terminal(),
-                Target::Stderr => io::stderr().is_terminal(),
-                Target::Writer => false, // unknown sink => assume no TTY
-            },
-        }
+}
+
+/// Colors are compiled out
+#[cfg(not(feature = "color"))]
+const fn auto_color(_: Target) -> bool {
+    false
+}
+
+/// Whether stdout / stderr is a terminal that renders ANSI. That doesn't change
+/// during a run, so each stream is checked once instead of on every record
+#[cfg(feature = "color")]
+fn tty_color(target: Target) -> bool {
+    static STDOUT: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    static STDERR: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    match target {
+        Target::Stdout => *STDOUT
+            .get_or_init(|| io::stdout().is_terminal() && vt_enabled(Target::Stdout)),
+        _ => *STDERR.get_or_init(|| io::stderr().is_terminal() && vt_enabled(Target::Stderr)),
     }
 }
 
 /// Returns the current logging level
 #[inline]
 pub fn level() -> Level {
-    level_from_u8(RUNTIME_LEVEL.load(Ordering::Relaxed))
+    GLOBAL.level()
 }
 /// Sets the current logging level
 pub fn set_level(l: Level) {
-    RUNTIME_LEVEL.store(l as u8, Ordering::Relaxed);
+    GLOBAL.set_level(l);
+}
+/// Makes the level one step more verbose (saturating at `Trace`) and returns it;
+/// safe to race, e.g. from a signal-driven "more logs" handler
+#[inline]
+#[allow(clippy::must_use_candidate)]
+pub fn bump_level() -> Level {
+    GLOBAL.bump_level()
+}
+/// Makes the level one step less verbose (saturating at `Fatal`) and returns it
+#[inline]
+#[allow(clippy::must_use_candidate)]
+pub fn reduce_level() -> Level {
+    GLOBAL.reduce_level()
+}
+/// Sets the level from a `-v` count (0 → `Warn`, 1 → `Info`, 2 → `Debug`,
+/// 3+ → `Trace`) and returns it
+#[inline]
+#[allow(clippy::must_use_candidate)]
+pub fn set_level_from_verbosity(v: u8) -> Level {
+    GLOBAL.set_level_from_verbosity(v)
+}
+/// Sets the level from a `-q` count (0 → `Warn`, 1 → `Error`, 2+ → `Fatal`) and
+/// returns it
+#[inline]
+#[allow(clippy::must_use_candidate)]
+pub fn set_level_from_quiet(q: u8) -> Level {
+    GLOBAL.set_level_from_quiet(q)
+}
+/// Sets the logging level until the returned guard is dropped
+#[inline]
+pub fn with_level(l: Level) -> LevelGuard<'static> {
+    GLOBAL.with_level(l)
+}
+/// Restores the previous runtime level when dropped (see [`with_level`])
+#[must_use = "the level is restored as soon as the guard is dropped"]
+pub struct LevelGuard<'a> {
+    logger: &'a Logger,
+    prev: Level,
+}
+impl Drop for LevelGuard<'_> {
+    fn drop(&mut self) {
+        self.logger.set_level(self.prev);
+    }
+}
+/// Master switch: `false` silences all records regardless of level
+pub fn set_enabled(on: bool) {
+    GLOBAL.set_enabled(on);
+}
+/// Returns `false` if logging was switched off with [`set_enabled`]
+#[inline]
+#[must_use]
+pub fn is_enabled() -> bool {
+    GLOBAL.is_enabled()
 }
 /// Show thread ids
 pub fn set_show_thread_id(on: bool) {
-    SHOW_TID.store(on, Ordering::Relaxed);
+    GLOBAL.set_show_thread_id(on);
+}
+/// Show the process id as `[pid:1234]`
+pub fn set_show_pid(on: bool) {
+    GLOBAL.set_show_pid(on);
+}
+/// Show the machine hostname as `[host:name]` (see [`Logger::set_show_hostname`])
+pub fn set_show_hostname(on: bool) {
+    GLOBAL.set_show_hostname(on);
+}
+/// Show the time elapsed since the previous line, e.g. `(+1.234 ms)`
+pub fn set_show_delta(on: bool) {
+    GLOBAL.set_show_delta(on);
+}
+/// Number lines as `#000042` to reconstruct their order (see [`Logger::set_show_seq`]
+/// for when the number can differ from the write order)
+pub fn set_show_seq(on: bool) {
+    GLOBAL.set_show_seq(on);
+}
+/// Render records straight into the sink under the emit lock instead of buffering
+/// each line first; saves memory for huge messages, slower for small ones (see
+/// [`Logger::set_direct_write`])
+pub fn set_direct_write(on: bool) {
+    GLOBAL.set_direct_write(on);
+}
+/// Escape control characters in messages (`\n`, `\t`, `\x1b`, ...) so every
+/// record stays on one line and can't inject ANSI sequences
+pub fn set_sanitize(on: bool) {
+    GLOBAL.set_sanitize(on);
+}
+/// Cap messages at `max` bytes; longer ones are cut on a char boundary and
+/// end with `…(truncated)`. `None` (the default) removes the cap
+pub fn set_max_message_len(max: Option<usize>) {
+    GLOBAL.set_max_message_len(max);
+}
+/// Returns the message length cap, if any
+#[inline]
+#[must_use]
+pub fn max_message_len() -> Option<usize> {
+    GLOBAL.max_message_len()
+}
+/// Returns whether control characters in messages are escaped
+#[inline]
+#[must_use]
+pub fn sanitize() -> bool {
+    GLOBAL.sanitize()
+}
+/// Returns whether the time since the previous line is shown
+#[inline]
+#[must_use]
+pub fn show_delta() -> bool {
+    GLOBAL.show_delta()
+}
+/// Returns whether lines carry a sequence number
+#[inline]
+#[must_use]
+pub fn show_seq() -> bool {
+    GLOBAL.show_seq()
+}
+/// Returns whether records are written straight into the sink
+#[inline]
+#[must_use]
+pub fn direct_write() -> bool {
+    GLOBAL.direct_write()
+}
+/// Returns whether the process id is shown
+#[inline]
+#[must_use]
+pub fn show_pid() -> bool {
+    GLOBAL.show_pid()
+}
+/// Returns whether the hostname is shown
+#[inline]
+#[must_use]
+pub fn show_hostname() -> bool {
+    GLOBAL.show_hostname()
+}
+/// Sets what the timestamp shows (see [`TimeSource`])
+pub fn set_time_source(src: TimeSource) {
+    GLOBAL.set_time_source(src);
+}
+/// Returns what the timestamp shows
+#[inline]
+#[must_use]
+pub fn time_source() -> TimeSource {
+    GLOBAL.time_source()
 }
 /// Show timestamps
 pub fn set_show_time(on: bool) {
-    SHOW_TIME.store(on, Ordering::Relaxed);
+    GLOBAL.set_show_time(on);
 }
 /// Show file and line
 pub fn set_show_file_line(on: bool) {
-    SHOW_FILE_LINE.store(on, Ordering::Relaxed);
+    GLOBAL.set_show_file_line(on);
 }
 /// Show group
 pub fn set_show_group(on: bool) {
-    SHOW_GROUP.store(on, Ordering::Relaxed);
+    GLOBAL.set_show_group(on);
+}
+/// Sets how source paths are shown (see [`PathStyle`])
+/// # Panics
+/// This function will panic if locking the setting fails
+pub fn set_path_style(style: PathStyle) {
+    GLOBAL.set_path_style(style);
+}
+/// Sets how the thread is labelled when thread ids are shown (see [`ThreadLabel`])
+pub fn set_thread_label(label: ThreadLabel) {
+    GLOBAL.set_thread_label(label);
+}
+/// Returns how the thread is labelled
+#[inline]
+#[must_use]
+pub fn thread_label() -> ThreadLabel {
+    GLOBAL.thread_label()
+}
+/// Show the module path of the call site as `{crate::module}`
+pub fn set_show_module(on: bool) {
+    GLOBAL.set_show_module(on);
+}
+/// Returns whether the module path is shown
+#[inline]
+#[must_use]
+pub fn show_module() -> bool {
+    GLOBAL.show_module()
+}
+/// Makes `<file:line>` a clickable OSC 8 hyperlink (`file://<abs>#L<line>`) in
+/// terminals that support it; only applies while colors are in use
+pub fn set_file_line_hyperlink(on: bool) {
+    GLOBAL.set_file_line_hyperlink(on);
+}
+/// Returns how source paths are shown
+/// # Panics
+/// This function will panic if locking the setting fails
+#[inline]
+#[must_use]
+pub fn path_style() -> PathStyle {
+    GLOBAL.path_style()
+}
+/// Returns whether thread ids are shown
+#[inline]
+#[must_use]
+pub fn show_thread_id() -> bool {
+    GLOBAL.show_thread_id()
+}
+/// Returns whether timestamps are shown
+#[inline]
+#[must_use]
+pub fn show_time() -> bool {
+    GLOBAL.show_time()
+}
+/// Returns whether file and line are shown
+#[inline]
+#[must_use]
+pub fn show_file_line() -> bool {
+    GLOBAL.show_file_line()
+}
+/// Returns whether the group tag is shown
+#[inline]
+#[must_use]
+pub fn show_group() -> bool {
+    GLOBAL.show_group()
 }
 /// Sets the color mode
 pub fn set_color_mode(mode: ColorMode) {
-    COLOR_MODE.store(mode as u8, Ordering::Relaxed);
+    GLOBAL.set_color_mode(mode);
+}
+/// Colors only records at `l` or above, e.g. `Warn` to leave info lines plain;
+/// the default `Trace` colors everything the color mode allows
+pub fn set_color_min_level(l: Level) {
+    GLOBAL.set_color_min_level(l);
+}
+/// Sets the line format (see [`DefaultFormat`] for the built-in layout)
+/// # Panics
+/// This function will panic if locking the format fails
+pub fn set_format(f: Box<dyn Format + Send + Sync>) {
+    GLOBAL.set_format(f);
+}
+/// Drops records for which `f` returns `false`; it runs after the level check and
+/// before rendering (see [`Logger::set_filter`])
+/// # Panics
+/// This function will panic if locking the filter fails
+pub fn set_filter(f: Box<dyn Fn(&Record) -> bool + Send + Sync>) {
+    GLOBAL.set_filter(f);
+}
+/// Removes the filter set with [`set_filter`]
+/// # Panics
+/// This function will panic if locking the filter fails
+pub fn clear_filter() {
+    GLOBAL.clear_filter();
+}
+/// Calls `f` with the error whenever writing a record to the sink fails, instead
+/// of dropping it silently (see [`Logger::set_on_error`])
+/// # Panics
+/// This function will panic if locking the callback fails
+pub fn set_on_error(f: Box<dyn Fn(io::Error) + Send + Sync>) {
+    GLOBAL.set_on_error(f);
+}
+/// Removes the callback set with [`set_on_error`]
+/// # Panics
+/// This function will panic if locking the callback fails
+pub fn clear_on_error() {
+    GLOBAL.clear_on_error();
+}
+/// Number of lines at `l` the global logger has written since startup or the last
+/// [`reset_counts`] (see [`Logger::emitted_count`])
+#[inline]
+#[must_use]
+pub fn emitted_count(l: Level) -> u64 {
+    GLOBAL.emitted_count(l)
+}
+/// Zeroes the global per-level counters
+pub fn reset_counts() {
+    GLOBAL.reset_counts();
+}
+/// Callback for [`set_observer`]: the level and group of each emitted record
+pub type Observer = Box<dyn Fn(Level, Option<&str>) + Send + Sync>;
+/// Calls `f` with the level and group of every record that gets past the level
+/// checks and the filter, right after it is written (see [`Logger::set_observer`])
+/// # Panics
+/// This function will panic if locking the observer fails
+pub fn set_observer(f: Observer) {
+    GLOBAL.set_observer(f);
+}
+/// Removes the observer set with [`set_observer`]
+/// # Panics
+/// This function will panic if locking the observer fails
+pub fn clear_observer() {
+    GLOBAL.clear_observer();
+}
+
+/// Names a sink added with [`register_sink`], for a [`set_sink_router`] callback
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SinkId(pub &'static str);
+impl SinkId {
+    /// The regular target (see [`set_target`]); never a registered sink
+    pub const DEFAULT: Self = Self("");
+}
+/// Callback for [`set_sink_router`]: picks the sink for a record
+pub type SinkRouter = Box<dyn Fn(&Record) -> SinkId + Send + Sync>;
+/// Picks the sink for each record by calling `f`, e.g. group `"audit"` to a
+/// dedicated file; ids without a registered sink mean the regular target (see
+/// [`Logger::set_sink_router`])
+/// # Panics
+/// This function will panic if locking the router fails
+pub fn set_sink_router(f: SinkRouter) {
+    GLOBAL.set_sink_router(f);
+}
+/// Removes the router set with [`set_sink_router`]
+/// # Panics
+/// This function will panic if locking the router fails
+pub fn clear_sink_router() {
+    GLOBAL.clear_sink_router();
+}
+/// Adds `w` as the sink a router selects with `id` (see [`Logger::register_sink`])
+/// # Panics
+/// This function will panic if locking the sink registry fails
+pub fn register_sink(id: SinkId, w: Box<dyn Write + Send>) {
+    GLOBAL.register_sink(id, w);
+}
+/// Removes the sink registered under `id`; returns whether there was one
+/// # Panics
+/// This function will panic if locking the sink registry fails
+#[allow(clippy::must_use_candidate)]
+pub fn unregister_sink(id: SinkId) -> bool {
+    GLOBAL.unregister_sink(id)
+}
+
+/// Set the clock used for timestamps (defaults to [`SystemClock`])
+/// # Panics
+/// Panics if the clock lock is poisoned
+pub fn set_clock(c: Box<dyn Clock + Send + Sync>) {
+    *CLOCK.write().unwrap() = Some(Arc::from(c));
+}
+#[inline]
+fn now_unix_nanos() -> i128 {
+    CLOCK
+        .read()
+        .unwrap()
+        .as_ref()
+        .map_or_else(|| SystemClock.now_unix_nanos(), |c| c.now_unix_nanos())
 }
+#[inline]
+fn now() -> SystemTime {
+    clock::to_system_time(now_unix_nanos())
+}
+/// Monotonic nanoseconds since the first call
+fn mono_nanos() -> u64 {
+    static EPOCH: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    u64::try_from(EPOCH.get_or_init(Instant::now).elapsed().as_nanos()).unwrap_or(u64::MAX)
+}
+/// The machine hostname, read once: `$HOSTNAME`, then `$COMPUTERNAME` (set on
+/// Windows), then `/etc/hostname`; empty if none of them is available
+fn hostname() -> &'static str {
+    static HOST: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    HOST.get_or_init(|| {
+        ["HOSTNAME", "COMPUTERNAME"]
+            .into_iter()
+            .find_map(|k| std::env::var(k).ok())
+            .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+            .map(|h| h.trim().to_string())
+            .unwrap_or_default()
+    })
+}
+/// Sets the level for a group, overriding the global level.
+///
+/// Groups are matched by `::`-separated prefix; the longest matching rule wins
+/// (`net::http` is governed by a `net` rule unless a `net::http` rule exists).
+/// # Panics
+/// This function will panic if locking the group rules fails
+pub fn set_group_level(group: &'static str, l: Level) {
+    GLOBAL.set_group_level(group, l);
+}
+/// Removes all per-group level overrides
+/// # Panics
+/// This function will panic if locking the group rules fails
+pub fn clear_group_levels() {
+    GLOBAL.clear_group_levels();
+}
+/// An environment variable read by [`try_init_from_env`] holds a value that
+/// doesn't parse, or names a log file that can't be opened
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EnvError {
+    /// Not a level name (`trace` … `fatal`)
+    Level {
+        /// Name of the variable
+        var: String,
+        /// The rejected value
+        value: String,
+    },
+    /// Not a color mode (`auto`, `always`, `never`)
+    Color {
+        /// Name of the variable
+        var: String,
+        /// The rejected value
+        value: String,
+    },
+    /// Not a target (`stdout`, `stderr`)
+    Target {
+        /// Name of the variable
+        var: String,
+        /// The rejected value
+        value: String,
+    },
+    /// Not a format (`human`, `logfmt`, or `json` with the `json` feature)
+    Format {
+        /// Name of the variable
+        var: String,
+        /// The rejected value
+        value: String,
+    },
+    /// The log file could not be opened
+    File {
+        /// Path from the variable
+        path: std::path::PathBuf,
+        /// Why opening failed
+        source: io::Error,
+    },
+}
+
+impl core::fmt::Display for EnvError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Level { var, value } => write!(
+                f,
+                "{var}={value:?} is not a log level (trace, debug, info, notice, warn, error, fatal)"
+            ),
+            Self::Color { var, value } => {
+                write!(
+                    f,
+                    "{var}={value:?} is not a color mode (auto, always, never)"
+                )
+            }
+            Self::Target { var, value } => {
+                write!(f, "{var}={value:?} is not a target (stdout, stderr)")
+            }
+            Self::Format { var, value } => {
+                write!(f, "{var}={value:?} is not a format (human, json, logfmt)")
+            }
+            Self::File { path, source } => {
+                write!(f, "cannot open log file {}: {source}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::File { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// `RUST_LOG_TARGET` value
+fn env_target(s: &str) -> Option<Target> {
+    if s.eq_ignore_ascii_case("stdout") {
+        Some(Target::Stdout)
+    } else if s.eq_ignore_ascii_case("stderr") {
+        Some(Target::Stderr)
+    } else {
+        None
+    }
+}
+/// `RUST_LOG_FORMAT` value
+fn env_format(s: &str) -> Option<Box<dyn Format + Send + Sync>> {
+    if s.eq_ignore_ascii_case("human") {
+        Some(Box::new(DefaultFormat))
+    } else if s.eq_ignore_ascii_case("logfmt") {
+        Some(Box::new(LogfmtFormat))
+    } else {
+        #[cfg(feature = "json")]
+        if s.eq_ignore_ascii_case("json") {
+            return Some(Box::new(JsonFormat));
+        }
+        None
+    }
+}
+
+/// Reads `var` and parses it; `Err` carries the unparseable value
+fn env_value<T>(var: &str, parse: impl FnOnce(&str) -> Option<T>) -> Result<Option<T>, String> {
+    std::env::var(var).map_or(Ok(None), |s| parse(&s).map(Some).ok_or(s))
+}
+
 /// Initialize the logger from environment variables
+///
+/// `RUST_LOG` accepts `env_logger`-style directives (`info,net=debug,net::http=trace`);
+/// its bare level takes precedence over `RUST_LOG_LEVEL`.
+///
+/// The destination comes from `RUST_LOG_FILE` (a path, appended to) or
+/// `RUST_LOG_TARGET` (`stdout`/`stderr`); the file wins when both are set.
+/// `RUST_LOG_FORMAT` picks `human`, `json` or `logfmt`.
+///
+/// Unparseable values are ignored (an invalid `RUST_LOG_COLOR` means `auto`) and a
+/// file that can't be opened falls back to stderr; use [`try_init_from_env`] to
+/// get an error instead.
+/// # Panics
+/// This function will panic if locking the group rules fails
 pub fn init_from_env() {
-    if let Ok(s) = std::env::var("RUST_LOG_LEVEL") {
-        let l = match s.to_lowercase().as_str() {
-            "trace" => Level::Trace,
-            "debug" => Level::Debug,
-            "info" => Level::Info,
-            "warn" => Level::Warn,
-            "error" => Level::Error,
-            "fatal" => Level::Fatal,
-            _ => level(),
-        };
+    init_from_env_prefixed("RUST_LOG");
+}
+/// Like [`init_from_env`], but with a custom variable prefix
+///
+/// Reads `{prefix}_LEVEL`, `{prefix}_COLOR`, `{prefix}_SHOW_TID`, `{prefix}_SHOW_TIME`,
+/// `{prefix}_TARGET`, `{prefix}_FILE`, `{prefix}_FORMAT` and `{prefix}` (directives),
+/// e.g. `MYAPP_LEVEL=debug` with prefix `"MYAPP"`
+/// # Panics
+/// This function will panic if locking the group rules fails
+pub fn init_from_env_prefixed(prefix: &str) {
+    let _ = env_init(prefix, true);
+}
+/// Like [`init_from_env`], but fails without changing anything if a variable
+/// holds an unparseable value or `RUST_LOG_FILE` can't be opened
+/// # Errors
+/// Returns which variable was invalid and its value, or the file open error
+/// # Panics
+/// This function will panic if locking the group rules fails
+pub fn try_init_from_env() -> Result<(), EnvError> {
+    try_init_from_env_prefixed("RUST_LOG")
+}
+/// [`try_init_from_env`] with the variable names of [`init_from_env_prefixed`]
+/// # Errors
+/// Returns which variable was invalid and its value, or the file open error
+/// # Panics
+/// This function will panic if locking the group rules fails
+pub fn try_init_from_env_prefixed(prefix: &str) -> Result<(), EnvError> {
+    env_init(prefix, false)
+}
+/// Flushes the sink when dropped; returned by [`init`] and [`try_init`]
+#[must_use = "the sink is flushed when the guard drops; bind it with `let _guard = ...`"]
+#[derive(Debug)]
+pub struct InitGuard(());
+impl Drop for InitGuard {
+    fn drop(&mut self) {
+        let _ = flush();
+    }
+}
+/// One-shot setup: [`init_from_env`] plus [`install_panic_hook`]
+///
+/// Hold the returned guard in `main` so buffered records are flushed on return.
+/// Only the first call configures anything; later calls just return another guard.
+/// # Panics
+/// This function will panic if locking the group rules fails
+pub fn init() -> InitGuard {
+    init_once(|| {
+        init_from_env();
+        Ok(())
+    })
+    .unwrap_or(InitGuard(()))
+}
+/// [`init`] with the validation of [`try_init_from_env`]
+///
+/// Nothing is installed when the environment is invalid, so a later call can retry.
+/// # Errors
+/// Returns which variable was invalid and its value, or the file open error
+/// # Panics
+/// This function will panic if locking the group rules fails
+pub fn try_init() -> Result<InitGuard, EnvError> {
+    init_once(try_init_from_env)
+}
+fn init_once(env: impl FnOnce() -> Result<(), EnvError>) -> Result<InitGuard, EnvError> {
+    static DONE: StdMutex<bool> = StdMutex::new(false);
+    let mut done = DONE.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+    if !*done {
+        env()?;
+        install_panic_hook();
+        *done = true;
+    }
+    drop(done);
+    Ok(InitGuard(()))
+}
+fn env_init(prefix: &str, lenient: bool) -> Result<(), EnvError> {
+    let var = |name: &str| format!("{prefix}_{name}");
+    let (level_var, color_var) = (var("LEVEL"), var("COLOR"));
+    let (target_var, format_var) = (var("TARGET"), var("FORMAT"));
+    let invalid = |e: EnvError| if lenient { Ok(()) } else { Err(e) };
+    let lvl = match env_value(&level_var, |s| s.parse().ok()) {
+        Ok(l) => l,
+        Err(value) => {
+            invalid(EnvError::Level {
+                var: level_var,
+                value,
+            })?;
+            None
+        }
+    };
+    let color = match env_value(&color_var, |s| s.parse().ok()) {
+        Ok(m) => m,
+        Err(value) => {
+            invalid(EnvError::Color {
+                var: color_var,
+                value,
+            })?;
+            Some(ColorMode::Auto)
+        }
+    };
+    let target = match env_value(&target_var, env_target) {
+        Ok(t) => t,
+        Err(value) => {
+            invalid(EnvError::Target {
+                var: target_var,
+                value,
+            })?;
+            None
+        }
+    };
+    let format = match env_value(&format_var, env_format) {
+        Ok(f) => f,
+        Err(value) => {
+            invalid(EnvError::Format {
+                var: format_var,
+                value,
+            })?;
+            None
+        }
+    };
+    // opened last, so a bad value elsewhere doesn't leave a stray file behind
+    let mut file_failed = false;
+    let file = match std::env::var_os(var("FILE")) {
+        Some(p) => match FileOptions::default().open(&p) {
+            Ok(f) => Some(f),
+            Err(source) => {
+                invalid(EnvError::File {
+                    path: p.into(),
+                    source,
+                })?;
+                file_failed = true;
+                None
+            }
+        },
+        None => None,
+    };
+
+    if let Some(l) = lvl {
         set_level(l);
     }
-    if let Ok(s) = std::env::var("RUST_LOG_COLOR") {
-        set_color_mode(s.parse().unwrap_or(ColorMode::Auto));
+    if let Some(f) = file {
+        set_writer(Box::new(f));
+    } else if file_failed {
+        set_target(Target::Stderr);
+    } else if let Some(t) = target {
+        set_target(t);
+    }
+    if let Some(f) = format {
+        set_format(f);
     }
-    if let Ok(s) = std::env::var("RUST_LOG_SHOW_TID") {
+    if let Ok(s) = std::env::var(prefix) {
+        let (global, rules) = filter::parse_directives(&s);
+        if let Some(l) = global {
+            set_level(l);
+        }
+        for (g, l) in rules {
+            GLOBAL.group_levels().set(g, l);
+        }
+    }
+    #[cfg(feature = "color")]
+    read_env_color();
+    if let Some(m) = color {
+        set_color_mode(m);
+    }
+    if let Ok(s) = std::env::var(var("SHOW_TID")) {
         set_show_thread_id(s == "1" || s.eq_ignore_ascii_case("true"));
     }
-    if let Ok(s) = std::env::var("RUST_LOG_SHOW_TIME") {
+    if let Ok(s) = std::env::var(var("SHOW_TIME")) {
         set_show_time(s == "1" || s.eq_ignore_ascii_case("true"));
     }
+    Ok(())
 }
 
 /// Correct Gregorian Y-M-D from days since 1970-01-01
 #[inline]
-#[allow(dead_code)]
 const fn civil_from_days_utc(days_since_unix_epoch: i64) -> (i32, u32, u32) {
     // Howard Hinnant’s algorithm
     let z = days_since_unix_epoch + 719_468; // days since 0000-03-01
@@ -289,137 +1556,309 @@ const fn civil_from_days_utc(days_since_unix_epoch: i64) -> (i32, u32, u32) {
     (y as i32, m as u32, d as u32)
 }
 #[inline]
-fn write_timestamp(mut w: impl Write) {
+// local time always shows its offset, so `z_suffix` only applies to UTC
+#[cfg_attr(
+    any(not(feature = "timestamp"), feature = "localtime"),
+    allow(unused_variables)
+)]
+#[cfg_attr(not(feature = "timestamp"), allow(unused_mut))]
+fn write_timestamp(
+    mut w: impl Write,
+    now: SystemTime,
+    format: TimestampFormat,
+    precision: Precision,
+    z_suffix: bool,
+) {
     #[cfg(all(feature = "timestamp", not(feature = "localtime")))]
     {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
+        let now = now
+            .duration_since(SystemTime::UNIX_EPOCH)
             .unwrap_or_default();
+        #[allow(clippy::cast_possible_wrap)]
         let secs = now.as_secs() as i64;
-        let ms = now.subsec_millis();
 
         let days = secs.div_euclid(86_400);
         let sod = secs.rem_euclid(86_400);
-        let h = (sod / 3_600) as i64;
-        let m = (sod % 3_600 / 60) as i64;
-        let s = (sod % 60) as i64;
+        let h = sod / 3_600;
+        let m = sod % 3_600 / 60;
+        let s = sod % 60;
 
         let (year, month, day) = civil_from_days_utc(days);
-        let _ = write!(
-            w,
-            "{year:04}-{month:02}-{day:02} {h:02}:{m:02}:{s:02}.{ms:03}Z "
-        );
+        let rfc3339 = format == TimestampFormat::Rfc3339;
+        let sep = if rfc3339 { 'T' } else { ' ' };
+        let _ = write!(w, "{year:04}-{month:02}-{day:02}{sep}{h:02}:{m:02}:{s:02}");
+        write_subsec(&mut w, now.subsec_nanos(), precision);
+        if z_suffix {
+            let _ = w.write_all(b"Z");
+        } else if rfc3339 {
+            let _ = w.write_all(b"+00:00");
+        }
     }
     #[cfg(all(feature = "timestamp", feature = "localtime"))]
     {
         // Local time via `time` crate if you enable the `localtime` feature
-        static TS_FMT: OnceLock<Vec<time::format_description::FormatItem<'static>>> =
-            OnceLock::new();
-        let fmt = TS_FMT.get_or_init(|| {
-            time::format_description::parse(
-                "[year]-[month]-[day] [hour]:[minute]:[second].[subsecond digits:3]",
-            )
-            .expect("valid timestamp format description")
-        });
-
-        let now = std::time::SystemTime::now();
         let now: time::OffsetDateTime = now.into();
-        let now =
-            now.to_offset(time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC));
-        let _ = write!(w, "{} ", now.format(fmt).unwrap());
+        let now = now.to_offset(local_offset());
+        let rfc3339 = format == TimestampFormat::Rfc3339;
+        let _ = write!(
+            w,
+            "{:04}-{:02}-{:02}{}{:02}:{:02}:{:02}",
+            now.year(),
+            u8::from(now.month()),
+            now.day(),
+            if rfc3339 { 'T' } else { ' ' },
+            now.hour(),
+            now.minute(),
+            now.second()
+        );
+        write_subsec(&mut w, now.nanosecond(), precision);
+        if rfc3339 {
+            let (h, m, _) = now.offset().as_hms();
+            let sign = if now.offset().is_negative() { '-' } else { '+' };
+            let _ = write!(w, "{sign}{:02}:{:02}", h.unsigned_abs(), m.unsigned_abs());
+        }
     }
 }
-
-#[inline]
-fn write_tid(mut w: impl Write) {
-    if SHOW_TID.load(Ordering::Relaxed) {
-        #[cfg(feature = "thread-id")]
-        let _ = write!(w, " [{:?}]", std::thread::current().id());
+// Seconds east of UTC; `i32::MIN` until captured or set
+#[cfg(feature = "localtime")]
+static UTC_OFFSET: std::sync::atomic::AtomicI32 = std::sync::atomic::AtomicI32::new(i32::MIN);
+/// Sets the offset local timestamps are rendered in, in seconds east of UTC
+/// (e.g. `2 * 3600` for `+02:00`), instead of the one captured from the system.
+///
+/// Without this, the system offset is read once, on the first timestamp, because
+/// it can only be read reliably while the process is single-threaded; log
+/// something before spawning threads, or set the offset here.
+#[cfg(feature = "localtime")]
+pub fn set_utc_offset(seconds: i32) {
+    UTC_OFFSET.store(seconds, Ordering::Relaxed);
+}
+/// The offset used for local timestamps: the one from `set_utc_offset`, or the
+/// system offset captured on first use (`time` refuses to read it once other
+/// threads exist, which used to make every later timestamp silently UTC)
+#[cfg(all(feature = "timestamp", feature = "localtime"))]
+fn local_offset() -> time::UtcOffset {
+    let mut secs = UTC_OFFSET.load(Ordering::Relaxed);
+    if secs == i32::MIN {
+        let sys = time::UtcOffset::current_local_offset().unwrap_or(time::UtcOffset::UTC);
+        // keep an offset set concurrently by `set_utc_offset`
+        secs = match UTC_OFFSET.compare_exchange(
+            i32::MIN,
+            sys.whole_seconds(),
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
+            Ok(_) => sys.whole_seconds(),
+            Err(set) => set,
+        };
     }
+    time::UtcOffset::from_whole_seconds(secs).unwrap_or(time::UtcOffset::UTC)
+}
+/// Writes `.fff` for the configured precision (nothing for `Secs`)
+#[cfg(feature = "timestamp")]
+#[inline]
+fn write_subsec(mut w: impl Write, nanos: u32, precision: Precision) {
+    let _ = match precision {
+        Precision::Secs => Ok(()),
+        Precision::Millis => write!(w, ".{:03}", nanos / 1_000_000),
+        Precision::Micros => write!(w, ".{:06}", nanos / 1_000),
+        Precision::Nanos => write!(w, ".{nanos:09}"),
+    };
 }
 
 #[inline]
-fn write_level(mut w: impl Write, l: Level, use_color: bool) {
+// `Notice` is shown as `NOTE` so every label fits the five-column level field
+fn write_level(mut w: impl Write, l: Level, style: LevelStyle, code: Option<&str>) {
+    let width = if style == LevelStyle::Short { 1 } else { 5 };
+    let label = match style {
+        LevelStyle::Full if l == Level::Notice => "NOTE",
+        LevelStyle::Full => l.as_str(),
+        LevelStyle::Short => &l.as_str()[..1],
+        LevelStyle::Lower => match l {
+            Level::Trace => "trace",
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Notice => "note",
+            Level::Warn => "warn",
+            Level::Error => "error",
+            Level::Fatal => "fatal",
+        },
+    };
     #[cfg(feature = "color")]
-    if use_color {
-        let _ = write!(w, "{}{:<5}{}", level_color(l), level_name(l), color::RST);
+    if let Some(code) = code {
+        let _ = write!(w, "{code}{label:<width$}{}", color::RST);
         return;
     }
-    let _ = write!(w, "{:<5}", level_name(l));
+    #[cfg(not(feature = "color"))]
+    let _ = code;
+    let _ = write!(w, "{label:<width$}");
 }
 
-fn emit_raw_bytes(bytes: &[u8]) {
-    let _g = EMIT_LOCK.lock().unwrap();
-    match target() {
-        Target::Stdout => {
-            let _ = io::stdout().lock().write_all(bytes);
-        }
-        Target::Stderr => {
-            let _ = io::stderr().lock().write_all(bytes);
+/// Capacity kept by the per-thread line buffer between emits
+const LINE_BUF_RETAIN: usize = 8 * 1024;
+
+thread_local! {
+    static LINE_BUF: core::cell::RefCell<Vec<u8>> = const { core::cell::RefCell::new(Vec::new()) };
+}
+
+/// Runs `f` with a cleared, thread-local line buffer.
+///
+/// Falls back to a fresh `Vec` when the buffer is already borrowed (a `Display`
+/// impl that logs while being formatted) or the thread-local is gone.
+fn with_line_buf(f: impl FnOnce(&mut Vec<u8>)) {
+    let mut f = Some(f);
+    let _ = LINE_BUF.try_with(|cell| {
+        let Ok(mut buf) = cell.try_borrow_mut() else {
+            return;
+        };
+        if let Some(f) = f.take() {
+            buf.clear();
+            f(&mut buf);
         }
-        Target::Writer => {
-            if let Some(m) = WRITER.get() {
-                let mut w = m.lock().unwrap();
-                let _ = w.write_all(bytes);
-            }
+        if buf.capacity() > LINE_BUF_RETAIN {
+            buf.clear();
+            buf.shrink_to(LINE_BUF_RETAIN);
         }
+    });
+    if let Some(f) = f {
+        f(&mut Vec::new());
     }
 }
 
+#[inline]
+fn emit_raw_bytes(bytes: &[u8]) {
+    let _ = GLOBAL.write_raw(Level::Info, None, bytes);
+}
+
+/// Flushes the current sink.
+///
+/// Sinks wrapped in a `BufWriter` (or any other buffering writer) only reach their
+/// destination when flushed; call this before exiting, or from a panic hook, so
+/// buffered records are not lost.
+/// # Errors
+/// Returns the error reported by the sink's `flush`.
+pub fn flush() -> io::Result<()> {
+    GLOBAL.flush()
+}
+
 /// Emit a log message
+/// # Panics
+/// This function will panic if locking the format fails
+#[inline]
+pub fn emit(l: Level, group: Option<&str>, file: &str, line_no: u32, args: Arguments) {
+    emit_kv(l, group, "", file, line_no, args, &[]);
+}
+
+/// Emit a log message with its module path (`""` for none) and structured
+/// `key = value` fields
+/// # Panics
+/// This function will panic if locking the format fails
 #[inline]
-pub fn emit(
+pub fn emit_kv(
     l: Level,
-    group: Option<&'static str>,
-    file: &'static str,
+    group: Option<&str>,
+    module: &str,
+    file: &str,
     line_no: u32,
     args: Arguments,
+    fields: &[(&str, &dyn core::fmt::Display)],
 ) {
-    if !rt_enabled(l) {
-        return;
+    if GLOBAL.emit_record(l, group, &[], module, file, line_no, args, fields) {
+        fatal_exit(l);
     }
-    let use_color = use_color();
-    let mut buf = Vec::<u8>::new();
-
-    if SHOW_TIME.load(Ordering::Relaxed) {
-        write_timestamp(&mut buf);
-    }
-    write_level(&mut buf, l, use_color);
-    write_tid(&mut buf);
-    if SHOW_FILE_LINE.load(Ordering::Relaxed) {
-        let _ = write!(&mut buf, " <{file}:{line_no}>");
-    }
-    if SHOW_GROUP.load(Ordering::Relaxed) {
-        if let Some(g) = group {
-            #[cfg(feature = "color")]
-            if use_color {
-                let _ = write!(
-                    &mut buf,
-                    " [{}{}{}{}]",
-                    color::BOLD,
-                    level_color(l),
-                    g,
-                    color::RST
-                );
-            } else {
-                let _ = write!(&mut buf, " [{g}]");
-            }
-            #[cfg(not(feature = "color"))]
-            {
-                let _ = write!(&mut buf, " [{g}]");
-            }
+}
+
+/// Emit a log message tagged with several groups (see [`Logger::emit_groups_to`])
+/// # Panics
+/// This function will panic if locking the format fails
+#[inline]
+pub fn emit_groups(
+    l: Level,
+    groups: &[&str],
+    module: &str,
+    file: &str,
+    line_no: u32,
+    args: Arguments,
+) {
+    let (group, extra) = local::split_groups(groups);
+    if GLOBAL.emit_record(l, group, extra, module, file, line_no, args, &[]) {
+        fatal_exit(l);
+    }
+}
+
+/// Flush and exit after a written `Fatal` record if `set_fatal_exits` asks for it
+fn fatal_exit(l: Level) {
+    if l == Level::Fatal {
+        if let Ok(code) = i32::try_from(FATAL_EXIT.load(Ordering::Relaxed)) {
+            let _ = flush();
+            std::process::exit(code);
         }
     }
-    let _ = buf.write_all(b" ");
-    let _ = buf.write_fmt(args);
-    let _ = buf.write_all(b"\n");
-    emit_raw_bytes(&buf);
+}
+
+/// Like [`emit`], but returns the sink's write error instead of handing it to the
+/// [`set_on_error`] callback.
+///
+/// A `Fatal` record exits as with [`emit`] once it is written; if the write
+/// fails, the error is returned instead.
+/// # Errors
+/// Returns the error from writing the line to the sink
+/// # Panics
+/// This function will panic if locking the format fails
+#[inline]
+pub fn try_emit(
+    l: Level,
+    group: Option<&str>,
+    file: &str,
+    line_no: u32,
+    args: Arguments,
+) -> io::Result<()> {
+    if GLOBAL.try_emit_record(l, group, &[], "", file, line_no, None, args, &[])? {
+        fatal_exit(l);
+    }
+    Ok(())
+}
+
+// Exit code for `Fatal` records (see `set_fatal_exits`); anything outside `i32`
+// means "just log"
+static FATAL_EXIT: AtomicI64 = AtomicI64::new(i64::MIN);
+/// Makes `fatal!`/`fatal_group!` on the global logger flush the sink and exit the
+/// process with `code` after writing the line; `None` (the default) just logs.
+///
+/// Records that are never written (logging disabled, below the level, dropped by
+/// the filter) don't exit.
+pub fn set_fatal_exits(code: Option<i32>) {
+    FATAL_EXIT.store(code.map_or(i64::MIN, i64::from), Ordering::Relaxed);
+}
+
+/// Installs a panic hook that logs the panic at `Fatal` (group `"panic"`) with its
+/// location, flushes the sink, and then runs the previously installed hook.
+pub fn install_panic_hook() {
+    let prev = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let msg = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+        let (file, line) = info
+            .location()
+            .map_or(("<unknown>", 0), |l| (l.file(), l.line()));
+        GLOBAL.emit_to(
+            Level::Fatal,
+            Some("panic"),
+            file,
+            line,
+            format_args!("{msg}"),
+        );
+        let _ = flush();
+        prev(info);
+    }));
 }
 
 /// Emit a log message
 #[macro_export]
-macro_rules! __rustlog_log { ($lvl:expr, $grp:expr, $($t:tt)+) => {{ if $crate::ct_enabled($lvl) { $crate::emit($lvl, $grp, file!(), line!(), format_args!($($t)+)) } }} }
+macro_rules! __rustlog_log { ($lvl:expr, $grp:expr, $($t:tt)+) => {{ if $crate::ct_enabled($lvl) { $crate::emit_kv($lvl, $grp, module_path!(), file!(), line!(), format_args!($($t)+), &[]) } }} }
 /// trace
 #[macro_export]
 macro_rules! trace { ($($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Trace, None, $($t)+) } }
@@ -429,6 +1868,9 @@ macro_rules! debug { ($($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Debu
 /// info
 #[macro_export]
 macro_rules! info  { ($($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Info,  None, $($t)+) } }
+/// notice
+#[macro_export]
+macro_rules! notice { ($($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Notice, None, $($t)+) } }
 /// warning
 #[macro_export]
 macro_rules! warn  { ($($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Warn,  None, $($t)+) } }
@@ -447,6 +1889,9 @@ macro_rules! debug_group { ($grp:expr, $($t:tt)+) => { $crate::__rustlog_log!($c
 /// info group
 #[macro_export]
 macro_rules! info_group  { ($grp:expr, $($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Info,  Some($grp), $($t)+) } }
+/// notice group
+#[macro_export]
+macro_rules! notice_group { ($grp:expr, $($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Notice, Some($grp), $($t)+) } }
 /// warning group
 #[macro_export]
 macro_rules! warn_group  { ($grp:expr, $($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Warn,  Some($grp), $($t)+) } }
@@ -456,9 +1901,202 @@ macro_rules! error_group { ($grp:expr, $($t:tt)+) => { $crate::__rustlog_log!($c
 /// fatal group
 #[macro_export]
 macro_rules! fatal_group { ($grp:expr, $($t:tt)+) => { $crate::__rustlog_log!($crate::Level::Fatal, Some($grp), $($t)+) } }
+/// Emit a log message with several groups
+#[macro_export]
+macro_rules! __rustlog_log_groups { ($lvl:expr, $grps:expr, $($t:tt)+) => {{ if $crate::ct_enabled($lvl) { $crate::emit_groups($lvl, $grps, module_path!(), file!(), line!(), format_args!($($t)+)) } }} }
+/// trace with several groups, e.g. `trace_groups!(&["net", "retry"], "...")`
+#[macro_export]
+macro_rules! trace_groups { ($grps:expr, $($t:tt)+) => { $crate::__rustlog_log_groups!($crate::Level::Trace, $grps, $($t)+) } }
+/// debug with several groups
+#[macro_export]
+macro_rules! debug_groups { ($grps:expr, $($t:tt)+) => { $crate::__rustlog_log_groups!($crate::Level::Debug, $grps, $($t)+) } }
+/// info with several groups
+#[macro_export]
+macro_rules! info_groups  { ($grps:expr, $($t:tt)+) => { $crate::__rustlog_log_groups!($crate::Level::Info,  $grps, $($t)+) } }
+/// notice with several groups
+#[macro_export]
+macro_rules! notice_groups { ($grps:expr, $($t:tt)+) => { $crate::__rustlog_log_groups!($crate::Level::Notice, $grps, $($t)+) } }
+/// warn with several groups
+#[macro_export]
+macro_rules! warn_groups  { ($grps:expr, $($t:tt)+) => { $crate::__rustlog_log_groups!($crate::Level::Warn,  $grps, $($t)+) } }
+/// error with several groups
+#[macro_export]
+macro_rules! error_groups { ($grps:expr, $($t:tt)+) => { $crate::__rustlog_log_groups!($crate::Level::Error, $grps, $($t)+) } }
+/// fatal with several groups
+#[macro_export]
+macro_rules! fatal_groups { ($grps:expr, $($t:tt)+) => { $crate::__rustlog_log_groups!($crate::Level::Fatal, $grps, $($t)+) } }
+/// Emit a log message with trailing `key = value` fields
+#[macro_export]
+macro_rules! __rustlog_log_kv { ($lvl:expr, $grp:expr, $msg:literal $(, $k:ident = $v:expr)* $(,)?) => {{
+    if $crate::ct_enabled($lvl) {
+        $crate::emit_kv($lvl, $grp, module_path!(), file!(), line!(), format_args!($msg), &[$((stringify!($k), &$v as &dyn ::core::fmt::Display)),*])
+    }
+}} }
+/// trace with fields: `trace_kv!("msg", key = value, ...)`
+#[macro_export]
+macro_rules! trace_kv { ($($t:tt)+) => { $crate::__rustlog_log_kv!($crate::Level::Trace, None, $($t)+) } }
+/// debug with fields: `debug_kv!("msg", key = value, ...)`
+#[macro_export]
+macro_rules! debug_kv { ($($t:tt)+) => { $crate::__rustlog_log_kv!($crate::Level::Debug, None, $($t)+) } }
+/// info with fields: `info_kv!("msg", key = value, ...)`
+#[macro_export]
+macro_rules! info_kv  { ($($t:tt)+) => { $crate::__rustlog_log_kv!($crate::Level::Info,  None, $($t)+) } }
+/// notice with fields: `notice_kv!("msg", key = value, ...)`
+#[macro_export]
+macro_rules! notice_kv { ($($t:tt)+) => { $crate::__rustlog_log_kv!($crate::Level::Notice, None, $($t)+) } }
+/// warn with fields: `warn_kv!("msg", key = value, ...)`
+#[macro_export]
+macro_rules! warn_kv  { ($($t:tt)+) => { $crate::__rustlog_log_kv!($crate::Level::Warn,  None, $($t)+) } }
+/// error with fields: `error_kv!("msg", key = value, ...)`
+#[macro_export]
+macro_rules! error_kv { ($($t:tt)+) => { $crate::__rustlog_log_kv!($crate::Level::Error, None, $($t)+) } }
+/// fatal with fields: `fatal_kv!("msg", key = value, ...)`
+#[macro_export]
+macro_rules! fatal_kv { ($($t:tt)+) => { $crate::__rustlog_log_kv!($crate::Level::Fatal, None, $($t)+) } }
+
+/// Emit a log message at most once per call site
+#[macro_export]
+macro_rules! __rustlog_log_once { ($lvl:expr, $grp:expr, $($t:tt)+) => {{
+    static ONCE: ::core::sync::atomic::AtomicBool = ::core::sync::atomic::AtomicBool::new(false);
+    if $crate::enabled($lvl, $grp) && !ONCE.swap(true, ::core::sync::atomic::Ordering::Relaxed) {
+        $crate::emit_kv($lvl, $grp, module_path!(), file!(), line!(), format_args!($($t)+), &[])
+    }
+}} }
+/// trace, once per call site
+#[macro_export]
+macro_rules! trace_once { ($($t:tt)+) => { $crate::__rustlog_log_once!($crate::Level::Trace, None, $($t)+) } }
+/// debug, once per call site
+#[macro_export]
+macro_rules! debug_once { ($($t:tt)+) => { $crate::__rustlog_log_once!($crate::Level::Debug, None, $($t)+) } }
+/// info, once per call site
+#[macro_export]
+macro_rules! info_once  { ($($t:tt)+) => { $crate::__rustlog_log_once!($crate::Level::Info,  None, $($t)+) } }
+/// notice, once per call site
+#[macro_export]
+macro_rules! notice_once { ($($t:tt)+) => { $crate::__rustlog_log_once!($crate::Level::Notice, None, $($t)+) } }
+/// warn, once per call site
+#[macro_export]
+macro_rules! warn_once  { ($($t:tt)+) => { $crate::__rustlog_log_once!($crate::Level::Warn,  None, $($t)+) } }
+/// error, once per call site
+#[macro_export]
+macro_rules! error_once { ($($t:tt)+) => { $crate::__rustlog_log_once!($crate::Level::Error, None, $($t)+) } }
+/// fatal, once per call site
+#[macro_export]
+macro_rules! fatal_once { ($($t:tt)+) => { $crate::__rustlog_log_once!($crate::Level::Fatal, None, $($t)+) } }
+/// Emit a log message on the 1st, (n+1)th, (2n+1)th, ... call at this call site
+#[macro_export]
+macro_rules! __rustlog_log_every_n { ($n:expr, $lvl:expr, $grp:expr, $($t:tt)+) => {{
+    static COUNT: ::core::sync::atomic::AtomicUsize = ::core::sync::atomic::AtomicUsize::new(0);
+    if $crate::enabled($lvl, $grp)
+        && COUNT.fetch_add(1, ::core::sync::atomic::Ordering::Relaxed) % ::core::cmp::max($n, 1usize) == 0
+    {
+        $crate::emit_kv($lvl, $grp, module_path!(), file!(), line!(), format_args!($($t)+), &[])
+    }
+}} }
+/// trace, every n-th call per call site
+#[macro_export]
+macro_rules! trace_every_n { ($n:expr, $($t:tt)+) => { $crate::__rustlog_log_every_n!($n, $crate::Level::Trace, None, $($t)+) } }
+/// debug, every n-th call per call site
+#[macro_export]
+macro_rules! debug_every_n { ($n:expr, $($t:tt)+) => { $crate::__rustlog_log_every_n!($n, $crate::Level::Debug, None, $($t)+) } }
+/// info, every n-th call per call site
+#[macro_export]
+macro_rules! info_every_n  { ($n:expr, $($t:tt)+) => { $crate::__rustlog_log_every_n!($n, $crate::Level::Info,  None, $($t)+) } }
+/// notice, every n-th call per call site
+#[macro_export]
+macro_rules! notice_every_n { ($n:expr, $($t:tt)+) => { $crate::__rustlog_log_every_n!($n, $crate::Level::Notice, None, $($t)+) } }
+/// warn, every n-th call per call site
+#[macro_export]
+macro_rules! warn_every_n  { ($n:expr, $($t:tt)+) => { $crate::__rustlog_log_every_n!($n, $crate::Level::Warn,  None, $($t)+) } }
+/// error, every n-th call per call site
+#[macro_export]
+macro_rules! error_every_n { ($n:expr, $($t:tt)+) => { $crate::__rustlog_log_every_n!($n, $crate::Level::Error, None, $($t)+) } }
+/// fatal, every n-th call per call site
+#[macro_export]
+macro_rules! fatal_every_n { ($n:expr, $($t:tt)+) => { $crate::__rustlog_log_every_n!($n, $crate::Level::Fatal, None, $($t)+) } }
+/// Emit a log message only if `cond` holds; the condition is checked first, so the
+/// arguments aren't evaluated when it is false
+#[macro_export]
+macro_rules! __rustlog_log_if { ($cond:expr, $lvl:expr, $grp:expr, $($t:tt)+) => {{
+    if $cond {
+        $crate::__rustlog_log!($lvl, $grp, $($t)+)
+    }
+}} }
+/// trace, only if the condition holds
+#[macro_export]
+macro_rules! trace_if { ($cond:expr, $($t:tt)+) => { $crate::__rustlog_log_if!($cond, $crate::Level::Trace, None, $($t)+) } }
+/// debug, only if the condition holds
+#[macro_export]
+macro_rules! debug_if { ($cond:expr, $($t:tt)+) => { $crate::__rustlog_log_if!($cond, $crate::Level::Debug, None, $($t)+) } }
+/// info, only if the condition holds
+#[macro_export]
+macro_rules! info_if  { ($cond:expr, $($t:tt)+) => { $crate::__rustlog_log_if!($cond, $crate::Level::Info,  None, $($t)+) } }
+/// notice, only if the condition holds
+#[macro_export]
+macro_rules! notice_if { ($cond:expr, $($t:tt)+) => { $crate::__rustlog_log_if!($cond, $crate::Level::Notice, None, $($t)+) } }
+/// warn, only if the condition holds
+#[macro_export]
+macro_rules! warn_if  { ($cond:expr, $($t:tt)+) => { $crate::__rustlog_log_if!($cond, $crate::Level::Warn,  None, $($t)+) } }
+/// error, only if the condition holds
+#[macro_export]
+macro_rules! error_if { ($cond:expr, $($t:tt)+) => { $crate::__rustlog_log_if!($cond, $crate::Level::Error, None, $($t)+) } }
+/// fatal, only if the condition holds
+#[macro_export]
+macro_rules! fatal_if { ($cond:expr, $($t:tt)+) => { $crate::__rustlog_log_if!($cond, $crate::Level::Fatal, None, $($t)+) } }
+
+/// Tag every record logged without a group in a scope
+///
+/// `scope_group!(group)` tags the rest of the scope, `scope_group!(group, { .. })` tags the
+/// block and evaluates to its value. Nested scopes stack, the innermost winning.
+#[macro_export]
+macro_rules! scope_group {
+    ($grp:expr) => {
+        let _scope_group_guard = $crate::push_group($grp);
+    };
+    ($grp:expr, $body:block) => {{
+        let _scope_group_guard = $crate::push_group($grp);
+        $body
+    }};
+}
+
+/// Indent the messages logged in a scope
+///
+/// `scope!()` indents the rest of the scope, `scope!({ .. })` indents the block and evaluates
+/// to its value. Each nesting adds [`indent_width`] spaces before the message.
+#[macro_export]
+macro_rules! scope {
+    () => {
+        let _scope_indent_guard = $crate::indent_guard();
+    };
+    ($body:block) => {{
+        let _scope_indent_guard = $crate::indent_guard();
+        $body
+    }};
+}
+
 /// Time a block
+///
+/// `scope_time!(label)` times the rest of the scope, `scope_time!(label, { .. })` times the
+/// block and evaluates to its value. Prefix with `level: Level::Debug,` to log at another level,
+/// or add `min: Duration` after the label to skip scopes faster than the threshold.
 #[macro_export]
 macro_rules! scope_time {
+    ($label:expr, min: $min:expr) => {
+        let _scope_time_guard = $crate::TimerGuard::new_at_min($label, $min, file!(), line!());
+    };
+    ($label:expr, min: $min:expr, $body:block) => {{
+        let _scope_time_guard = $crate::TimerGuard::new_at_min($label, $min, file!(), line!());
+        $body
+    }};
+    (level: $lvl:expr, $label:expr) => {
+        let _scope_time_guard =
+            $crate::TimerGuard::new_at($label, file!(), line!()).with_level($lvl);
+    };
+    (level: $lvl:expr, $label:expr, $body:block) => {{
+        let _scope_time_guard =
+            $crate::TimerGuard::new_at($label, file!(), line!()).with_level($lvl);
+        $body
+    }};
     ($label:expr) => {
         let _scope_time_guard = $crate::TimerGuard::new_at($label, file!(), line!());
     };
@@ -467,8 +2105,121 @@ macro_rules! scope_time {
         $body
     }};
 }
+/// Start a [`Stopwatch`] tagged with `label`: `let sw = stopwatch!("deploy"); ..; sw.lap("build");`
+#[macro_export]
+macro_rules! stopwatch {
+    ($label:expr) => {
+        $crate::Stopwatch::new_at($label, file!(), line!())
+    };
+}
+/// Time a block, log it like `scope_time!`, and evaluate to `(value, Duration)`
+#[macro_export]
+macro_rules! measure {
+    (level: $lvl:expr, $label:expr, $body:block) => {{
+        let _measure_guard = $crate::TimerGuard::new_at($label, file!(), line!()).with_level($lvl);
+        let _measure_value = $body;
+        (_measure_value, _measure_guard.finish())
+    }};
+    ($label:expr, $body:block) => {
+        $crate::measure!(level: $crate::Level::Info, $label, $body)
+    };
+}
+/// Log an error with its whole `source()` chain at `Error`
+///
+/// `error_chain!(err)` logs `outer <- cause <- root cause`, `error_chain!(err, "saving {}", id)`
+/// prefixes it with `saving 7: `. `err` is anything implementing `std::error::Error`.
+#[macro_export]
+macro_rules! error_chain {
+    ($err:expr) => {
+        $crate::__rustlog_log!($crate::Level::Error, None, "{}", $crate::ErrorChain::new(&$err))
+    };
+    ($err:expr, $($t:tt)+) => {
+        $crate::__rustlog_log!(
+            $crate::Level::Error,
+            None,
+            "{}: {}",
+            format_args!($($t)+),
+            $crate::ErrorChain::new(&$err)
+        )
+    };
+}
+/// Displays an error followed by its `source()` chain, joined with ` <- `
+#[derive(Clone, Copy)]
+pub struct ErrorChain<'a>(&'a (dyn std::error::Error + 'a));
+impl<'a> ErrorChain<'a> {
+    /// Wrap an error for display
+    #[inline]
+    #[must_use]
+    pub const fn new(e: &'a (dyn std::error::Error + 'a)) -> Self {
+        Self(e)
+    }
+}
+impl core::fmt::Display for ErrorChain<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)?;
+        let mut cause = self.0.source();
+        while let Some(e) = cause {
+            write!(f, " <- {e}")?;
+            cause = e.source();
+        }
+        Ok(())
+    }
+}
+impl core::fmt::Debug for ErrorChain<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+/// Displays bytes as a `hexdump -C` style dump, 16 bytes per row:
+/// `00000000  48 65 6c 6c 6f 0a 77 6f  72 6c 64 0a              |Hello.world.|`
+///
+/// Rows are separated by `\n` with none after the last one.
+#[derive(Debug, Clone, Copy)]
+pub struct HexDump<'a>(pub &'a [u8]);
+impl core::fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        const WIDTH: usize = 16;
+        for (row, chunk) in self.0.chunks(WIDTH).enumerate() {
+            if row > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{:08x} ", row * WIDTH)?;
+            for i in 0..WIDTH {
+                if i % 8 == 0 {
+                    f.write_str(" ")?;
+                }
+                match chunk.get(i) {
+                    Some(b) => write!(f, "{b:02x} ")?,
+                    None => f.write_str("   ")?,
+                }
+            }
+            f.write_str(" |")?;
+            for &b in chunk {
+                let c = if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+            f.write_str("|")?;
+        }
+        Ok(())
+    }
+}
+
 /// Human readable duration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct HumanDuration(pub std::time::Duration);
+impl HumanDuration {
+    /// Wrap a `Duration` for display
+    #[inline]
+    #[must_use]
+    pub const fn new(d: std::time::Duration) -> Self {
+        Self(d)
+    }
+}
 impl core::fmt::Display for HumanDuration {
     fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let d = self.0;
@@ -505,7 +2256,16 @@ impl core::fmt::Display for HumanDuration {
             let m = (rem % 3_600) / 60;
             let s = rem % 60;
             let ms = nanos / 1_000_000;
-            write!(formatter, "{days}d {h:02}h{m:02}m{s:02}.{ms:03}s")
+            if days >= 365 {
+                write!(formatter, "{}y", days / 365)?;
+            }
+            if days >= 7 {
+                let weeks = (days % 365) / 7;
+                let wdays = (days % 365) % 7;
+                write!(formatter, "{weeks}w{wdays}d {h:02}h{m:02}m{s:02}.{ms:03}s")
+            } else {
+                write!(formatter, "{days}d {h:02}h{m:02}m{s:02}.{ms:03}s")
+            }
         }
     }
 }
@@ -518,6 +2278,8 @@ impl From<std::time::Duration> for HumanDuration {
 /// Timer guard
 pub struct TimerGuard {
     label: &'static str,
+    level: Level,
+    min: std::time::Duration,
     start: Instant,
     file: &'static str,
     line: u32,
@@ -527,34 +2289,160 @@ impl TimerGuard {
     #[inline]
     #[must_use]
     pub fn new_at(label: &'static str, file: &'static str, line: u32) -> Self {
+        Self::new_at_min(label, std::time::Duration::ZERO, file, line)
+    }
+    /// Create a new timer guard that only logs if the scope took at least `min`
+    #[inline]
+    #[must_use]
+    pub fn new_at_min(
+        label: &'static str,
+        min: std::time::Duration,
+        file: &'static str,
+        line: u32,
+    ) -> Self {
         Self {
             label,
+            level: Level::Info,
+            min,
             start: Instant::now(),
             file,
             line,
         }
     }
+    /// Set the level the timing is logged at (default `Info`)
+    #[inline]
+    #[must_use]
+    pub const fn with_level(mut self, l: Level) -> Self {
+        self.level = l;
+        self
+    }
+    /// Time elapsed so far
+    #[inline]
+    #[must_use]
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.start.elapsed()
+    }
+    /// Restart the timer; the logged time is measured from the last reset
+    #[inline]
+    pub fn reset(&mut self) {
+        self.start = Instant::now();
+    }
+    /// Log the timing now and return it
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    pub fn finish(self) -> std::time::Duration {
+        let elapsed = self.start.elapsed();
+        self.log(elapsed);
+        std::mem::forget(self);
+        elapsed
+    }
+    fn log(&self, elapsed: std::time::Duration) {
+        emit(
+            self.level,
+            Some(self.label),
+            self.file,
+            self.line,
+            format_args!("took {}", HumanDuration(elapsed)),
+        );
+    }
 }
 impl Drop for TimerGuard {
     fn drop(&mut self) {
         let elapsed = self.start.elapsed();
+        if elapsed >= self.min {
+            self.log(elapsed);
+        }
+    }
+}
+
+/// Timer for linear phases: each [`lap`](Self::lap) logs the time since the
+/// previous lap (or the start), and dropping it logs the total
+pub struct Stopwatch {
+    label: &'static str,
+    level: Level,
+    start: Instant,
+    last: core::cell::Cell<Instant>,
+    file: &'static str,
+    line: u32,
+}
+impl Stopwatch {
+    /// Start a stopwatch; its records are tagged with `label` as the group
+    #[inline]
+    #[must_use]
+    pub fn new_at(label: &'static str, file: &'static str, line: u32) -> Self {
+        let now = Instant::now();
+        Self {
+            label,
+            level: Level::Info,
+            start: now,
+            last: core::cell::Cell::new(now),
+            file,
+            line,
+        }
+    }
+    /// Set the level laps and the total are logged at (default `Info`)
+    #[inline]
+    #[must_use]
+    pub const fn with_level(mut self, l: Level) -> Self {
+        self.level = l;
+        self
+    }
+    /// Log `"<phase> took <lap> (total <elapsed>)"` and return the lap time
+    #[allow(clippy::must_use_candidate)]
+    pub fn lap(&self, phase: &str) -> std::time::Duration {
+        let now = Instant::now();
+        let lap = now.duration_since(self.last.replace(now));
         emit(
-            Level::Info,
+            self.level,
             Some(self.label),
             self.file,
             self.line,
-            format_args!("took {}", HumanDuration(elapsed)),
+            format_args!(
+                "{phase} took {} (total {})",
+                HumanDuration(lap),
+                HumanDuration(now.duration_since(self.start))
+            ),
+        );
+        lap
+    }
+    /// Time since the stopwatch started
+    #[inline]
+    #[must_use]
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.start.elapsed()
+    }
+}
+impl Drop for Stopwatch {
+    fn drop(&mut self) {
+        emit(
+            self.level,
+            Some(self.label),
+            self.file,
+            self.line,
+            format_args!("total {}", HumanDuration(self.start.elapsed())),
         );
     }
 }
 
-/// Emit a banner
+/// Emit a `"<name> v<version>"` banner line
 #[inline]
 pub fn banner_with(name: &str, version: &str) {
-    emit_raw_bytes(name.as_bytes());
-    emit_raw_bytes(b" v");
-    emit_raw_bytes(version.as_bytes());
-    emit_raw_bytes(b"\n");
+    // one write so the line can't interleave with records from other threads
+    with_line_buf(|buf| {
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(b" v");
+        buf.extend_from_slice(version.as_bytes());
+        buf.extend_from_slice(eol().as_bytes());
+        emit_raw_bytes(buf);
+    });
+}
+
+/// Emit a banner with `rustlog`'s own name and version.
+///
+/// Kept for backward compatibility; use [`banner!`] to print the calling crate's.
+#[inline]
+pub fn banner() {
+    banner_with(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
 }
 
 #[macro_export]
@@ -574,6 +2462,7 @@ mod tests {
     use core::time::Duration as StdDuration;
 
     #[test]
+    #[allow(unknown_lints, clippy::duration_suboptimal_units)]
     fn human_duration_formats_all_ranges() {
         assert_eq!(
             format!("{}", HumanDuration(StdDuration::from_nanos(500))),
@@ -606,5 +2495,30 @@ mod tests {
             format!("{}", HumanDuration(StdDuration::from_secs(2 * 86_400 + 5))),
             "2d 00h00m05.000s"
         );
+        assert_eq!(
+            format!(
+                "{}",
+                HumanDuration(StdDuration::from_secs(9 * 86_400 + 4 * 3600 + 5 * 60 + 6))
+            ),
+            "1w2d 04h05m06.000s"
+        );
+        assert_eq!(
+            format!("{}", HumanDuration(StdDuration::from_secs(40 * 86_400))),
+            "5w5d 00h00m00.000s"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                HumanDuration(StdDuration::from_millis(400 * 86_400_000 + 3_600_250))
+            ),
+            "1y5w0d 01h00m00.250s"
+        );
+    }
+
+    #[test]
+    fn human_duration_constructors_agree() {
+        let d = StdDuration::from_millis(1_234);
+        assert_eq!(HumanDuration::new(d), HumanDuration::from(d));
+        assert_eq!(HumanDuration::from(d).to_string(), "1.234 s");
     }
 }
@@ -1,49 +1,228 @@
 use core::fmt::Arguments;
-use std::io::{self, IsTerminal, Write};
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
-use std::sync::{Arc, Mutex as StdMutex};
-use std::time::Instant;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 
 // Pull from crate root
-use crate::EMIT_LOCK;
-#[cfg(feature = "color")]
-use crate::{color, level_color};
-use crate::{ct_enabled, write_level, write_timestamp, ColorMode, HumanDuration, Level, Target};
+use crate::filter::GroupLevels;
+use crate::sink::{DailyFile, RotatingFile};
+use crate::sync::Mutex;
+use crate::{
+    ct_enabled, with_line_buf, ColorMode, ColorScope, Eol, FileOptions, Format, GroupColorMode,
+    HumanDuration, Level, LevelGuard, LevelStyle, Observer, PathStyle, Precision, Record, SinkId, SinkRouter, Target,
+    ThreadLabel, TimeSource, TimestampFormat,
+};
 
 /// Local logger
 pub struct Logger {
     level: AtomicU8,
+    on: AtomicBool,
     show_tid: AtomicBool,
+    thread_label: AtomicU8,
+    time_source: AtomicU8,
+    ts_precision: AtomicU8,
+    ts_z_suffix: AtomicBool,
+    ts_format: AtomicU8,
+    show_pid: AtomicBool,
+    show_hostname: AtomicBool,
+    show_delta: AtomicBool,
+    show_seq: AtomicBool,
+    /// Number of the last line that got a sequence number
+    seq: AtomicU64,
+    direct_write: AtomicBool,
+    sanitize: AtomicBool,
+    /// `usize::MAX` means no limit
+    max_message_len: AtomicUsize,
+    /// `crate::mono_nanos` of the previous emit, 0 if none since delta output was enabled
+    last_emit: AtomicU64,
     show_time: AtomicBool,
     show_group: AtomicBool,
     show_file_line: AtomicBool,
+    file_link: AtomicBool,
+    show_module: AtomicBool,
     color_mode: AtomicU8,
-    sink: StdMutex<Sink>,
+    /// Records below this level are never colored
+    color_min_level: AtomicU8,
+    eol: AtomicU8,
+    level_style: AtomicU8,
+    color_scope: AtomicU8,
+    group_color_mode: AtomicU8,
+    group_bold: AtomicBool,
+    dim_metadata: AtomicBool,
+    indent_width: AtomicUsize,
+    /// `None` until a layout setting changes (see [`Layout::DEFAULT`])
+    layout: RwLock<Option<Arc<Layout>>>,
+    on_error: RwLock<Option<ErrorFn>>,
+    /// Sinks a router can pick by id, besides the regular target
+    sinks: RwLock<Vec<(SinkId, SharedWriter)>>,
+    group_levels: GroupLevels,
+    counts: LevelCounts,
+    sink: Mutex<Sink>,
+    // keeps each line whole; per logger so unrelated loggers don't contend
+    emit_lock: Mutex<()>,
+}
+
+type SharedWriter = Arc<Mutex<Box<dyn Write + Send>>>;
+type FilterFn = Arc<dyn Fn(&Record) -> bool + Send + Sync>;
+type ErrorFn = Arc<dyn Fn(io::Error) + Send + Sync>;
+type ObserverFn = Arc<dyn Fn(Level, Option<&str>) + Send + Sync>;
+type RouterFn = Arc<dyn Fn(&Record) -> SinkId + Send + Sync>;
+
+thread_local! {
+    // set while an `on_error` callback runs, so a failing sink it logs to
+    // can't recurse back into it
+    static IN_ON_ERROR: core::cell::Cell<bool> = const { core::cell::Cell::new(false) };
+}
+
+/// Lines written per level, indexed by `Level as usize`
+struct LevelCounts([AtomicU64; 7]);
+impl LevelCounts {
+    const fn new() -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const ZERO: AtomicU64 = AtomicU64::new(0);
+        Self([ZERO; 7])
+    }
 }
 
 struct Sink {
-    target: Target,
-    writer: Option<Arc<StdMutex<Box<dyn Write + Send>>>>,
+    // `None` means "not configured yet" (defaults to `Stderr`)
+    target: Option<Target>,
+    writer: Option<SharedWriter>,
+}
+
+/// The settings each record reads that hold more than an atomic; they rarely
+/// change, so setters swap in an updated copy and an emit reads them all at once
+#[derive(Clone)]
+struct Layout {
+    format: Option<Arc<dyn Format + Send + Sync>>,
+    filter: Option<FilterFn>,
+    observer: Option<ObserverFn>,
+    router: Option<RouterFn>,
+    path_style: PathStyle,
+    field_sep: &'static str,
+    group_delims: (&'static str, &'static str),
+    default_group: Option<&'static str>,
+    level_colors: [Option<&'static str>; 7],
+}
+impl Layout {
+    const DEFAULT: Self = Self {
+        format: None,
+        filter: None,
+        observer: None,
+        router: None,
+        path_style: PathStyle::Full,
+        field_sep: " ",
+        group_delims: ("[", "]"),
+        default_group: None,
+        level_colors: [None; 7],
+    };
+
+    /// The escape to use for `l`, or `""` when colors are compiled out
+    const fn level_color(&self, l: Level) -> &'static str {
+        #[cfg(feature = "color")]
+        match self.level_colors[l as usize] {
+            Some(code) => code,
+            None => crate::level_color(l),
+        }
+        #[cfg(not(feature = "color"))]
+        {
+            let _ = (self, l);
+            ""
+        }
+    }
+}
+
+/// Hands `inner` whole lines only, each in one write, holding back a partial line
+/// however long it gets (a `LineWriter` splits lines longer than its buffer)
+struct WholeLines<W: Write> {
+    inner: W,
+    line: Vec<u8>,
+}
+impl<W: Write> Write for WholeLines<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(end) = buf.iter().rposition(|&b| b == b'\n') else {
+            self.line.extend_from_slice(buf);
+            return Ok(buf.len());
+        };
+        let (lines, rest) = buf.split_at(end + 1);
+        let res = if self.line.is_empty() {
+            self.inner.write_all(lines)
+        } else {
+            self.line.extend_from_slice(lines);
+            self.inner.write_all(&self.line)
+        };
+        self.line.clear();
+        self.line.extend_from_slice(rest);
+        res.map(|()| buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
 }
 
 impl Default for Logger {
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The first of `groups` and the rest
+pub(crate) fn split_groups<'a>(groups: &'a [&'a str]) -> (Option<&'a str>, &'a [&'a str]) {
+    groups
+        .split_first()
+        .map_or((None, &[][..]), |(g, rest)| (Some(*g), rest))
+}
+
+impl Logger {
+    /// The default configuration; `const` so the global logger can be a plain `static`
+    pub(crate) const fn new() -> Self {
         Self {
             level: AtomicU8::new(Level::Info as u8),
+            on: AtomicBool::new(true),
             show_tid: AtomicBool::new(cfg!(feature = "thread-id")),
+            thread_label: AtomicU8::new(ThreadLabel::Id as u8),
+            time_source: AtomicU8::new(TimeSource::Wall as u8),
+            ts_precision: AtomicU8::new(Precision::Millis as u8),
+            ts_z_suffix: AtomicBool::new(true),
+            ts_format: AtomicU8::new(TimestampFormat::SpaceSeparated as u8),
+            show_pid: AtomicBool::new(false),
+            show_hostname: AtomicBool::new(false),
+            show_delta: AtomicBool::new(false),
+            show_seq: AtomicBool::new(false),
+            seq: AtomicU64::new(0),
+            direct_write: AtomicBool::new(false),
+            sanitize: AtomicBool::new(false),
+            max_message_len: AtomicUsize::new(usize::MAX),
+            last_emit: AtomicU64::new(0),
             show_time: AtomicBool::new(cfg!(feature = "timestamp")),
             show_group: AtomicBool::new(true),
             show_file_line: AtomicBool::new(cfg!(feature = "file-line")),
+            file_link: AtomicBool::new(false),
+            show_module: AtomicBool::new(false),
             color_mode: AtomicU8::new(ColorMode::Auto as u8),
-            sink: StdMutex::new(Sink {
-                target: Target::Stderr,
+            color_min_level: AtomicU8::new(Level::Trace as u8),
+            eol: AtomicU8::new(Eol::Lf as u8),
+            level_style: AtomicU8::new(LevelStyle::Full as u8),
+            color_scope: AtomicU8::new(ColorScope::LevelOnly as u8),
+            group_color_mode: AtomicU8::new(GroupColorMode::ByLevel as u8),
+            group_bold: AtomicBool::new(true),
+            dim_metadata: AtomicBool::new(false),
+            indent_width: AtomicUsize::new(2),
+            layout: RwLock::new(None),
+            on_error: RwLock::new(None),
+            sinks: RwLock::new(Vec::new()),
+            group_levels: GroupLevels::new(),
+            counts: LevelCounts::new(),
+            sink: Mutex::new(Sink {
+                target: None,
                 writer: None,
             }),
+            emit_lock: Mutex::new(()),
         }
     }
-}
 
-impl Logger {
     #[inline]
     #[must_use]
     /// Create a new `LoggerBuilder`
@@ -51,6 +230,123 @@ impl Logger {
         LoggerBuilder::default()
     }
 
+    /// A new logger starting from a snapshot of this one's configuration (level,
+    /// toggles, colors, format, filter, group levels, ...), for per-subsystem loggers
+    /// that diverge through the usual setters.
+    ///
+    /// The child writes to the same sink as the parent (a shared writer is shared,
+    /// not reopened) until `set_target`/`set_writer` is called on either of them;
+    /// later configuration changes on one don't affect the other
+    /// # Panics
+    /// This function will panic if locking the parent's configuration fails
+    #[must_use]
+    pub fn child(&self) -> Self {
+        let flag = |a: &AtomicBool| AtomicBool::new(a.load(Ordering::Relaxed));
+        let byte = |a: &AtomicU8| AtomicU8::new(a.load(Ordering::Relaxed));
+        let parent = self.sink.lock();
+        let sink = Sink {
+            target: parent.target,
+            writer: parent.writer.clone(),
+        };
+        drop(parent);
+        Self {
+            level: byte(&self.level),
+            on: flag(&self.on),
+            show_tid: flag(&self.show_tid),
+            thread_label: byte(&self.thread_label),
+            time_source: byte(&self.time_source),
+            ts_precision: byte(&self.ts_precision),
+            ts_z_suffix: flag(&self.ts_z_suffix),
+            ts_format: byte(&self.ts_format),
+            show_pid: flag(&self.show_pid),
+            show_hostname: flag(&self.show_hostname),
+            show_delta: flag(&self.show_delta),
+            show_seq: flag(&self.show_seq),
+            seq: AtomicU64::new(0),
+            direct_write: flag(&self.direct_write),
+            sanitize: flag(&self.sanitize),
+            max_message_len: AtomicUsize::new(self.max_message_len.load(Ordering::Relaxed)),
+            last_emit: AtomicU64::new(0),
+            show_time: flag(&self.show_time),
+            show_group: flag(&self.show_group),
+            show_file_line: flag(&self.show_file_line),
+            file_link: flag(&self.file_link),
+            show_module: flag(&self.show_module),
+            color_mode: byte(&self.color_mode),
+            color_min_level: byte(&self.color_min_level),
+            eol: byte(&self.eol),
+            level_style: byte(&self.level_style),
+            color_scope: byte(&self.color_scope),
+            group_color_mode: byte(&self.group_color_mode),
+            group_bold: flag(&self.group_bold),
+            dim_metadata: flag(&self.dim_metadata),
+            indent_width: AtomicUsize::new(self.indent_width()),
+            layout: RwLock::new(self.layout()),
+            on_error: RwLock::new(self.on_error.read().unwrap().clone()),
+            sinks: RwLock::new(self.sinks.read().unwrap().clone()),
+            group_levels: self.group_levels.snapshot(),
+            counts: LevelCounts::new(),
+            sink: Mutex::new(sink),
+            emit_lock: Mutex::new(()),
+        }
+    }
+
+    /// Overwrite this logger's configuration and sink with `other`'s; per-group
+    /// levels are kept
+    pub(crate) fn assign_from(&self, other: &Self) {
+        let copy =
+            |a: &AtomicBool, b: &AtomicBool| a.store(b.load(Ordering::Relaxed), Ordering::Relaxed);
+        let copy_byte =
+            |a: &AtomicU8, b: &AtomicU8| a.store(b.load(Ordering::Relaxed), Ordering::Relaxed);
+        copy_byte(&self.level, &other.level);
+        copy(&self.on, &other.on);
+        copy(&self.show_tid, &other.show_tid);
+        copy_byte(&self.thread_label, &other.thread_label);
+        copy_byte(&self.time_source, &other.time_source);
+        copy_byte(&self.ts_precision, &other.ts_precision);
+        copy(&self.ts_z_suffix, &other.ts_z_suffix);
+        copy_byte(&self.ts_format, &other.ts_format);
+        copy(&self.show_pid, &other.show_pid);
+        copy(&self.show_hostname, &other.show_hostname);
+        copy(&self.show_delta, &other.show_delta);
+        copy(&self.show_seq, &other.show_seq);
+        copy(&self.direct_write, &other.direct_write);
+        copy(&self.sanitize, &other.sanitize);
+        self.set_max_message_len(other.max_message_len());
+        self.last_emit.store(0, Ordering::Relaxed);
+        copy(&self.show_time, &other.show_time);
+        copy(&self.show_group, &other.show_group);
+        copy(&self.show_file_line, &other.show_file_line);
+        copy(&self.file_link, &other.file_link);
+        copy(&self.show_module, &other.show_module);
+        copy_byte(&self.color_mode, &other.color_mode);
+        copy_byte(&self.color_min_level, &other.color_min_level);
+        copy_byte(&self.eol, &other.eol);
+        copy_byte(&self.level_style, &other.level_style);
+        copy_byte(&self.color_scope, &other.color_scope);
+        copy_byte(&self.group_color_mode, &other.group_color_mode);
+        copy(&self.group_bold, &other.group_bold);
+        copy(&self.dim_metadata, &other.dim_metadata);
+        self.set_indent_width(other.indent_width());
+        let layout = other.layout();
+        *self.layout.write().unwrap() = layout;
+        self.on_error
+            .write()
+            .unwrap()
+            .clone_from(&other.on_error.read().unwrap());
+        self.sinks
+            .write()
+            .unwrap()
+            .clone_from(&other.sinks.read().unwrap());
+        let src = other.sink.lock();
+        let sink = Sink {
+            target: src.target,
+            writer: src.writer.clone(),
+        };
+        drop(src);
+        *self.sink.lock() = sink;
+    }
+
     // configuration
     #[inline]
     /// Set the log level
@@ -58,11 +354,158 @@ impl Logger {
         self.level.store(l as u8, Ordering::Relaxed);
     }
     #[inline]
+    /// Set the log level until the returned guard is dropped
+    pub fn with_level(&self, l: Level) -> LevelGuard<'_> {
+        let prev = crate::level_from_u8(self.level.swap(l as u8, Ordering::Relaxed));
+        LevelGuard { logger: self, prev }
+    }
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    /// Make the level one step more verbose (`Fatal` → `Error` → … → `Trace`,
+    /// saturating) and return the new level; atomic, so concurrent callers
+    /// each take one step
+    pub fn bump_level(&self) -> Level {
+        self.step_level(|x| x.checked_sub(1))
+    }
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    /// Make the level one step less verbose (`Trace` → `Debug` → … → `Fatal`,
+    /// saturating) and return the new level
+    pub fn reduce_level(&self) -> Level {
+        self.step_level(|x| (x < Level::Fatal as u8).then_some(x + 1))
+    }
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    /// Set the level from a `-v` count: 0 → `Warn`, 1 → `Info`, 2 → `Debug`,
+    /// 3 or more → `Trace`; returns it
+    pub fn set_level_from_verbosity(&self, v: u8) -> Level {
+        let l = match v {
+            0 => Level::Warn,
+            1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        };
+        self.set_level(l);
+        l
+    }
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    /// Set the level from a `-q` count, going the other way from the same `Warn`
+    /// base: 0 → `Warn`, 1 → `Error`, 2 or more → `Fatal`; returns it
+    pub fn set_level_from_quiet(&self, q: u8) -> Level {
+        let l = match q {
+            0 => Level::Warn,
+            1 => Level::Error,
+            _ => Level::Fatal,
+        };
+        self.set_level(l);
+        l
+    }
+    fn step_level(&self, step: impl Fn(u8) -> Option<u8>) -> Level {
+        let new = match self
+            .level
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, &step)
+        {
+            Ok(prev) => step(prev).unwrap_or(prev),
+            Err(cur) => cur,
+        };
+        crate::level_from_u8(new)
+    }
+    #[inline]
+    /// Master switch: `false` silences all records regardless of level
+    pub fn set_enabled(&self, on: bool) {
+        self.on.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    #[must_use]
+    /// Returns `false` if logging was switched off with `set_enabled`
+    pub fn is_enabled(&self) -> bool {
+        self.on.load(Ordering::Relaxed)
+    }
+    #[inline]
     /// Set whether to show thread ids
     pub fn set_show_thread_id(&self, on: bool) {
         self.show_tid.store(on, Ordering::Relaxed);
     }
     #[inline]
+    /// Set whether to show the process id
+    pub fn set_show_pid(&self, on: bool) {
+        self.show_pid.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether to show the hostname. It is taken from `$HOSTNAME`, then
+    /// `$COMPUTERNAME`, then `/etc/hostname`, and read only once per process
+    pub fn set_show_hostname(&self, on: bool) {
+        self.show_hostname.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether to show the time elapsed since the previous line, e.g. `(+1.234 ms)`
+    pub fn set_show_delta(&self, on: bool) {
+        self.last_emit.store(0, Ordering::Relaxed);
+        self.show_delta.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether to number lines `#000001`, `#000002`, ... so interleaved output
+    /// from several threads can be put back in order. Only numbered lines count,
+    /// and toggling doesn't restart the numbering.
+    ///
+    /// With direct writes the number is taken under the emit lock, so it follows
+    /// the order lines reach the sink exactly. Otherwise it is taken when the line
+    /// is rendered, just before the lock, and two threads racing for the sink can
+    /// write their lines in the opposite order.
+    ///
+    /// A number is used up even if its line never arrives (the write fails, or an
+    /// `AsyncWriter` with `DropNewest` drops it), so the sequence is gap-free only
+    /// among successfully written lines
+    pub fn set_show_seq(&self, on: bool) {
+        self.show_seq.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether records are rendered straight into the sink while the emit lock
+    /// is held, instead of into a per-thread buffer that is written in one go.
+    ///
+    /// Lines stay whole either way: direct mode holds back each line until its
+    /// newline and hands it to the sink in one write, so line-oriented sinks such as
+    /// [`RotatingFile`](crate::sink::RotatingFile) never see part of a line. It
+    /// avoids holding a copy of a whole multi-line record, but holds the lock while
+    /// formatting, so the buffered default is faster for ordinary lines
+    pub fn set_direct_write(&self, on: bool) {
+        self.direct_write.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set what the timestamp shows (see [`TimeSource`])
+    pub fn set_time_source(&self, src: TimeSource) {
+        self.time_source.store(src as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set the fractional second digits of wall-clock timestamps
+    pub fn set_timestamp_precision(&self, p: Precision) {
+        self.ts_precision.store(p as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether UTC timestamps end with `Z` (the default)
+    pub fn set_timestamp_z_suffix(&self, on: bool) {
+        self.ts_z_suffix.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set the layout of wall-clock timestamps
+    pub fn set_timestamp_format(&self, f: TimestampFormat) {
+        self.ts_format.store(f as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether control characters in messages are escaped (`\n`, `\t`, `\x1b`, ...)
+    /// so every record stays on one line and can't inject ANSI sequences
+    pub fn set_sanitize(&self, on: bool) {
+        self.sanitize.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Cap messages at `max` bytes (cut on a char boundary and marked with
+    /// `…(truncated)`); `None` removes the cap
+    pub fn set_max_message_len(&self, max: Option<usize>) {
+        self.max_message_len
+            .store(max.unwrap_or(usize::MAX), Ordering::Relaxed);
+    }
+    #[inline]
     /// Set whether to show timestamps
     pub fn set_show_time(&self, on: bool) {
         self.show_time.store(on, Ordering::Relaxed);
@@ -77,146 +520,948 @@ impl Logger {
     pub fn set_show_file_line(&self, on: bool) {
         self.show_file_line.store(on, Ordering::Relaxed);
     }
+    /// Set how source paths are shown
+    /// # Panics
+    /// This function will panic if locking the setting fails
+    pub fn set_path_style(&self, style: PathStyle) {
+        self.update_layout(|l| l.path_style = style);
+    }
+    #[inline]
+    /// Set how the thread is labelled when thread ids are shown
+    pub fn set_thread_label(&self, label: ThreadLabel) {
+        self.thread_label.store(label as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether to show the module path
+    pub fn set_show_module(&self, on: bool) {
+        self.show_module.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Make `<file:line>` a clickable OSC 8 link when colors are in use
+    pub fn set_file_line_hyperlink(&self, on: bool) {
+        self.file_link.store(on, Ordering::Relaxed);
+    }
     #[inline]
     /// Set the color mode
     pub fn set_color_mode(&self, m: ColorMode) {
         self.color_mode.store(m as u8, Ordering::Relaxed);
     }
+    #[inline]
+    /// Color only records at `l` or above; lower ones are written without ANSI
+    /// escapes even when the color mode would use them (default `Trace`: all)
+    pub fn set_color_min_level(&self, l: Level) {
+        self.color_min_level.store(l as u8, Ordering::Relaxed);
+    }
+    /// Set what the default format puts between the prefix fields (timestamp,
+    /// level, `[tid]`, `<file:line>`, `[group]`, ...) and before the message;
+    /// default `" "`
+    /// # Panics
+    /// This function will panic if locking the setting fails
+    pub fn set_field_separator(&self, sep: &'static str) {
+        self.update_layout(|l| l.field_sep = sep);
+    }
+    /// Set the strings the default format wraps group tags in; default `"["` and
+    /// `"]"`
+    /// # Panics
+    /// This function will panic if locking the setting fails
+    pub fn set_group_delimiters(&self, open: &'static str, close: &'static str) {
+        self.update_layout(|l| l.group_delims = (open, close));
+    }
+    #[inline]
+    /// Set the line ending written after each record (default [`Eol::Lf`]).
+    ///
+    /// Only the record's final `\n` is rewritten, by both the buffered and the
+    /// direct-write path; newlines inside the message stay `\n`
+    pub fn set_eol(&self, e: Eol) {
+        self.eol.store(e as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set how the default format renders levels (see [`LevelStyle`])
+    pub fn set_level_style(&self, s: LevelStyle) {
+        self.level_style.store(s as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set how much of a line is colored when colors are on (see [`ColorScope`])
+    pub fn set_color_scope(&self, s: ColorScope) {
+        self.color_scope.store(s as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set how the `[group]` tag is colored when colors are on (see [`GroupColorMode`])
+    pub fn set_group_color_mode(&self, m: GroupColorMode) {
+        self.group_color_mode.store(m as u8, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether a colored `[group]` tag is also bold (the default)
+    ///
+    /// Some terminals render bold as a brighter color; turning it off keeps just the color.
+    pub fn set_group_bold(&self, on: bool) {
+        self.group_bold.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set whether the default format renders the timestamp, thread id and
+    /// `<file:line>` faint when colors are in use, so the message stands out; off
+    /// by default
+    pub fn set_dim_metadata(&self, on: bool) {
+        self.dim_metadata.store(on, Ordering::Relaxed);
+    }
+    #[inline]
+    /// Set how many spaces each nested `scope!` indents the message by (default 2)
+    pub fn set_indent_width(&self, spaces: usize) {
+        self.indent_width.store(spaces, Ordering::Relaxed);
+    }
 
+    // current configuration
     #[inline]
-    /// Set the target
+    #[must_use]
+    /// Returns the log level
+    pub fn level(&self) -> Level {
+        crate::level_from_u8(self.level.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether thread ids are shown
+    pub fn show_thread_id(&self) -> bool {
+        self.show_tid.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether the process id is shown
+    pub fn show_pid(&self) -> bool {
+        self.show_pid.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether the hostname is shown
+    pub fn show_hostname(&self) -> bool {
+        self.show_hostname.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns what the timestamp shows
+    pub fn time_source(&self) -> TimeSource {
+        TimeSource::from(self.time_source.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the fractional second digits of wall-clock timestamps
+    pub fn timestamp_precision(&self) -> Precision {
+        crate::precision_from_u8(self.ts_precision.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether UTC timestamps end with `Z`
+    pub fn timestamp_z_suffix(&self) -> bool {
+        self.ts_z_suffix.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the layout of wall-clock timestamps
+    pub fn timestamp_format(&self) -> TimestampFormat {
+        TimestampFormat::from(self.ts_format.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether control characters in messages are escaped
+    pub fn sanitize(&self) -> bool {
+        self.sanitize.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the message length cap, if any
+    pub fn max_message_len(&self) -> Option<usize> {
+        Some(self.max_message_len.load(Ordering::Relaxed)).filter(|&n| n != usize::MAX)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether the time since the previous line is shown
+    pub fn show_delta(&self) -> bool {
+        self.show_delta.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether records are written straight into the sink
+    pub fn direct_write(&self) -> bool {
+        self.direct_write.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether lines carry a sequence number
+    pub fn show_seq(&self) -> bool {
+        self.show_seq.load(Ordering::Relaxed)
+    }
+    /// The next sequence number, starting at 1
+    fn next_seq(&self) -> u64 {
+        self.seq.fetch_add(1, Ordering::Relaxed).wrapping_add(1)
+    }
+    /// Time since the previous emit, or `None` for the first line after delta
+    /// output was enabled. The swap makes every line's delta relative to exactly
+    /// one predecessor, even when several threads log at once
+    fn delta(&self) -> Option<Duration> {
+        let now = crate::mono_nanos().max(1);
+        match self.last_emit.swap(now, Ordering::Relaxed) {
+            0 => None,
+            prev => Some(Duration::from_nanos(now.saturating_sub(prev))),
+        }
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether timestamps are shown
+    pub fn show_time(&self) -> bool {
+        self.show_time.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether the group tag is shown
+    pub fn show_group(&self) -> bool {
+        self.show_group.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether file and line are shown
+    pub fn show_file_line(&self) -> bool {
+        self.show_file_line.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns how source paths are shown
+    /// # Panics
+    /// This function will panic if locking the setting fails
+    pub fn path_style(&self) -> PathStyle {
+        self.with_layout(|l| l.path_style)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the separator between prefix fields
+    /// # Panics
+    /// This function will panic if locking the setting fails
+    pub fn field_separator(&self) -> &'static str {
+        self.with_layout(|l| l.field_sep)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the opening and closing group tag delimiters
+    /// # Panics
+    /// This function will panic if locking the setting fails
+    pub fn group_delimiters(&self) -> (&'static str, &'static str) {
+        self.with_layout(|l| l.group_delims)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the line ending written after each record
+    pub fn eol(&self) -> Eol {
+        Eol::from(self.eol.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns how the default format renders levels
+    pub fn level_style(&self) -> LevelStyle {
+        LevelStyle::from(self.level_style.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns how much of a line is colored when colors are on
+    pub fn color_scope(&self) -> ColorScope {
+        ColorScope::from(self.color_scope.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns how the `[group]` tag is colored
+    pub fn group_color_mode(&self) -> GroupColorMode {
+        GroupColorMode::from(self.group_color_mode.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether a colored `[group]` tag is bold
+    pub fn group_bold(&self) -> bool {
+        self.group_bold.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether metadata is rendered faint
+    pub fn dim_metadata(&self) -> bool {
+        self.dim_metadata.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns how many spaces each nested `scope!` indents the message by
+    pub fn indent_width(&self) -> usize {
+        self.indent_width.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns how the thread is labelled
+    pub fn thread_label(&self) -> ThreadLabel {
+        ThreadLabel::from(self.thread_label.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether the module path is shown
+    pub fn show_module(&self) -> bool {
+        self.show_module.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns whether `<file:line>` is rendered as a hyperlink (when colors are in use)
+    pub fn file_line_hyperlink(&self) -> bool {
+        self.file_link.load(Ordering::Relaxed)
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the color mode
+    pub fn color_mode(&self) -> ColorMode {
+        ColorMode::from(self.color_mode.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the lowest level that gets colored
+    pub fn color_min_level(&self) -> Level {
+        crate::level_from_u8(self.color_min_level.load(Ordering::Relaxed))
+    }
+    #[inline]
+    #[must_use]
+    /// Returns the output target
+    pub fn target(&self) -> Target {
+        self.sink.lock().target.unwrap_or(Target::Stderr)
+    }
+
+    /// Set the line format
+    /// # Panics
+    /// This function will panic if locking the format fails
+    pub fn set_format(&self, f: Box<dyn Format + Send + Sync>) {
+        let f = Arc::from(f);
+        self.update_layout(|l| l.format = Some(f));
+    }
+
+    /// Drop records for which `f` returns `false`. It runs after the level check
+    /// and before rendering, and sees the group and file even when their output is
+    /// off; the message is available through `record.args`
+    /// # Panics
+    /// This function will panic if locking the filter fails
+    pub fn set_filter(&self, f: Box<dyn Fn(&Record) -> bool + Send + Sync>) {
+        let f = Arc::from(f);
+        self.update_layout(|l| l.filter = Some(f));
+    }
+    /// Remove the filter set with [`Self::set_filter`]
+    /// # Panics
+    /// This function will panic if locking the filter fails
+    pub fn clear_filter(&self) {
+        self.update_layout(|l| l.filter = None);
+    }
+
+    /// Call `f` with the error whenever writing a record to the sink fails
+    /// (instead of dropping it silently). Records that fail while `f` runs don't
+    /// call it again
+    /// # Panics
+    /// This function will panic if locking the callback fails
+    pub fn set_on_error(&self, f: Box<dyn Fn(io::Error) + Send + Sync>) {
+        *self.on_error.write().unwrap() = Some(Arc::from(f));
+    }
+    /// Remove the callback set with [`Self::set_on_error`]
+    /// # Panics
+    /// This function will panic if locking the callback fails
+    pub fn clear_on_error(&self) {
+        *self.on_error.write().unwrap() = None;
+    }
+
+    /// Number of lines at `l` this logger has written since it was created or
+    /// [`Self::reset_counts`] was last called; records dropped by the level checks
+    /// or the filter, and failed writes, aren't counted
+    #[must_use]
+    pub fn emitted_count(&self, l: Level) -> u64 {
+        self.counts.0[l as usize].load(Ordering::Relaxed)
+    }
+    /// Zero the per-level counters read by [`Self::emitted_count`]
+    pub fn reset_counts(&self) {
+        for c in &self.counts.0 {
+            c.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Call `f` with the level and group of every record that passes the level
+    /// checks and the filter, right after its line is written (even if the write
+    /// failed). Unlike a filter it can't suppress the line
+    /// # Panics
+    /// This function will panic if locking the observer fails
+    pub fn set_observer(&self, f: Observer) {
+        let f = Arc::from(f);
+        self.update_layout(|l| l.observer = Some(f));
+    }
+    /// Remove the observer set with [`Self::set_observer`]
+    /// # Panics
+    /// This function will panic if locking the observer fails
+    pub fn clear_observer(&self) {
+        self.update_layout(|l| l.observer = None);
+    }
+
+    /// Pick the sink for each record that passes the level checks and the filter:
+    /// `f` returns the id of a sink added with [`Self::register_sink`], or any
+    /// other id (such as [`SinkId::DEFAULT`]) for the regular target. Routed
+    /// records are colored as for a custom writer
+    /// # Panics
+    /// This function will panic if locking the router fails
+    pub fn set_sink_router(&self, f: SinkRouter) {
+        let f = Arc::from(f);
+        self.update_layout(|l| l.router = Some(f));
+    }
+    /// Remove the router set with [`Self::set_sink_router`]; every record goes to
+    /// the regular target again
+    /// # Panics
+    /// This function will panic if locking the router fails
+    pub fn clear_sink_router(&self) {
+        self.update_layout(|l| l.router = None);
+    }
+    /// Add `w` as the sink a router selects with `id`, replacing any sink already
+    /// registered under it. `SinkId::DEFAULT` always means the regular target, so
+    /// registering it does nothing
+    /// # Panics
+    /// This function will panic if locking the sink registry fails
+    pub fn register_sink(&self, id: SinkId, w: Box<dyn Write + Send>) {
+        if id == SinkId::DEFAULT {
+            return;
+        }
+        let w = Arc::new(Mutex::new(w));
+        let mut sinks = self.sinks.write().unwrap();
+        match sinks.iter_mut().find(|(i, _)| *i == id) {
+            Some(entry) => entry.1 = w,
+            None => sinks.push((id, w)),
+        }
+    }
+    /// Remove the sink registered under `id`; records routed to it go to the
+    /// regular target afterwards. Returns whether there was one
     /// # Panics
-    /// This function will panic if locking the sink fails
+    /// This function will panic if locking the sink registry fails
+    #[allow(clippy::must_use_candidate)]
+    pub fn unregister_sink(&self, id: SinkId) -> bool {
+        let mut sinks = self.sinks.write().unwrap();
+        let len = sinks.len();
+        sinks.retain(|(i, _)| *i != id);
+        len != sinks.len()
+    }
+    fn registered_sink(&self, id: SinkId) -> Option<SharedWriter> {
+        let sinks = self.sinks.read().unwrap();
+        sinks.iter().find(|(i, _)| *i == id).map(|(_, w)| w.clone())
+    }
+
+    #[inline]
+    /// Set the target
     pub fn set_target(&self, t: Target) {
-        self.sink.lock().unwrap().target = t;
+        self.sink.lock().target = Some(t);
+    }
+    /// Set the target only if none was selected yet; subsequent calls are ignored
+    pub fn set_target_once(&self, t: Target) {
+        self.sink.lock().target.get_or_insert(t);
+    }
+    /// Reset the target to the default (`Stderr`) and drop the custom writer, if any
+    pub fn reset_target(&self) {
+        let old = {
+            let mut s = self.sink.lock();
+            s.target = None;
+            s.writer.take()
+        };
+        drop(old);
     }
     /// Set the writer
-    /// # Panics
-    /// This function will panic if locking the sink fails
     pub fn set_writer(&self, w: Box<dyn Write + Send>) {
-        let arc = Arc::new(StdMutex::new(w));
-        let mut s = self.sink.lock().unwrap();
-        s.writer = Some(arc);
-        s.target = Target::Writer;
+        let arc = Arc::new(Mutex::new(w));
+        let old = {
+            let mut s = self.sink.lock();
+            s.target = Some(Target::Writer);
+            s.writer.replace(arc)
+        };
+        // close the previous sink outside of the lock
+        drop(old);
+    }
+    /// Install `w` (selecting `Target::Writer`) and hand back the writer it
+    /// replaces, if one was installed, so it can be restored later with another
+    /// `replace_writer`. The writer is swapped in place, so [`Self::child`] loggers
+    /// sharing it are redirected too
+    pub fn replace_writer(&self, w: Box<dyn Write + Send>) -> Option<Box<dyn Write + Send>> {
+        let mut s = self.sink.lock();
+        s.target = Some(Target::Writer);
+        if let Some(shared) = &s.writer {
+            return Some(std::mem::replace(&mut *shared.lock(), w));
+        }
+        s.writer = Some(Arc::new(Mutex::new(w)));
+        None
     }
     /// Set the output target to a file.
     /// # Errors
     /// This function will return an error if the file cannot be opened for writing.
-    pub fn set_file(&self, path: impl AsRef<std::path::Path>) -> io::Result<()> {
-        let f = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(path)?;
-        self.set_writer(Box::new(f));
+    pub fn set_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.set_file_with(path, FileOptions::default())
+    }
+    /// Set the output target to a file opened with `opts`.
+    /// # Errors
+    /// This function will return an error if the file cannot be opened for writing.
+    pub fn set_file_with(&self, path: impl AsRef<Path>, opts: FileOptions) -> io::Result<()> {
+        self.set_writer(Box::new(opts.open(path)?));
         Ok(())
     }
 
+    /// Flush the sink; call this before exit when writing through a buffered writer
+    /// # Errors
+    /// Returns the error reported by the sink's `flush`
+    pub fn flush(&self) -> io::Result<()> {
+        let (target, writer) = self.sink();
+        let _g = self.emit_lock.lock();
+        match target {
+            Target::Stdout => io::stdout().lock().flush(),
+            Target::Stderr => io::stderr().lock().flush(),
+            Target::Writer => writer.map_or(Ok(()), |w| w.lock().flush()),
+            Target::Split(_) => {
+                let out = io::stdout().lock().flush();
+                io::stderr().lock().flush().and(out)
+            }
+        }
+    }
+
+    /// Set the level for a group (longest `::`-separated prefix wins)
+    /// # Panics
+    /// This function will panic if locking the group rules fails
+    pub fn set_group_level(&self, group: &'static str, l: Level) {
+        self.group_levels.set(group, l);
+    }
+    /// Remove all per-group level overrides
+    /// # Panics
+    /// This function will panic if locking the group rules fails
+    pub fn clear_group_levels(&self) {
+        self.group_levels.clear();
+    }
+    /// Tag records logged without a group with `group` (`None` to stop)
+    /// # Panics
+    /// This function will panic if locking the default group fails
+    pub fn set_default_group(&self, group: Option<&'static str>) {
+        self.update_layout(|l| l.default_group = group);
+    }
+    /// Returns the group used for records logged without one
+    /// # Panics
+    /// This function will panic if locking the default group fails
+    #[must_use]
+    pub fn default_group(&self) -> Option<&'static str> {
+        self.with_layout(|l| l.default_group)
+    }
+    // `RUST_LOG` rules borrow the variable, so they bypass the `&'static str` setter
+    pub(crate) const fn group_levels(&self) -> &GroupLevels {
+        &self.group_levels
+    }
+    /// Override the ANSI escape used for a level (a full escape like `"\x1b[38;5;208m"`)
+    /// # Panics
+    /// This function will panic if locking the color table fails
+    pub fn set_level_color(&self, l: Level, ansi: &'static str) {
+        self.update_layout(|layout| layout.level_colors[l as usize] = Some(ansi));
+    }
+
+    #[inline]
+    #[must_use]
+    /// Returns `true` if records at `l` (in `group`) pass this logger's runtime filters
+    pub fn enabled(&self, l: Level, group: Option<&str>) -> bool {
+        self.is_enabled()
+            && self.level_passes(
+                l,
+                group
+                    .or_else(crate::context::current_group)
+                    .or_else(|| self.default_group()),
+            )
+    }
+    /// The level check for a record whose group is already resolved
+    fn level_passes(&self, l: Level, group: Option<&str>) -> bool {
+        let min = group
+            .and_then(|g| self.group_levels.level_for(g))
+            .map_or_else(|| self.level.load(Ordering::Relaxed), |g| g as u8);
+        (l as u8) >= min
+    }
     #[inline]
-    fn enabled(&self, l: Level) -> bool {
-        (l as u8) >= self.level.load(Ordering::Relaxed)
+    #[must_use]
+    /// Returns `true` if a record at `l` in `group` would be emitted, taking the
+    /// compile-time floor, the master switch and group levels into account
+    pub fn group_enabled(&self, group: &str, l: Level) -> bool {
+        ct_enabled(l) && self.enabled(l, Some(group))
     }
 
     /// Emit a log message
     /// # Panics
-    /// This function will panic if locking the sink fails
+    /// This function will panic if locking the format fails
     pub fn emit_to(
         &self,
         l: Level,
-        group: Option<&'static str>,
-        file: &'static str,
+        group: Option<&str>,
+        file: &str,
         line_no: u32,
         args: Arguments,
     ) {
-        if !self.enabled(l) || !ct_enabled(l) {
-            return;
+        self.emit_kv_to(l, group, "", file, line_no, args, &[]);
+    }
+
+    /// Emit `msg` verbatim; no format string is involved, so braces in text built
+    /// at runtime need no escaping
+    /// # Panics
+    /// This function will panic if locking the format fails
+    pub fn emit_str(&self, l: Level, group: Option<&str>, file: &str, line_no: u32, msg: &str) {
+        let args = format_args!("{msg}");
+        let res = self.try_emit_record(l, group, &[], "", file, line_no, Some(msg), args, &[]);
+        if let Err(e) = res {
+            self.report_error(e);
         }
+    }
 
-        let (target, writer) = {
-            let s = self.sink.lock().unwrap();
-            (s.target, s.writer.clone())
-        };
+    /// Emit a log message with its module path (`""` for none) and structured
+    /// `key = value` fields
+    /// # Panics
+    /// This function will panic if locking the format fails
+    #[allow(clippy::too_many_arguments)]
+    pub fn emit_kv_to(
+        &self,
+        l: Level,
+        group: Option<&str>,
+        module: &str,
+        file: &str,
+        line_no: u32,
+        args: Arguments,
+        fields: &[(&str, &dyn core::fmt::Display)],
+    ) {
+        self.emit_record(l, group, &[], module, file, line_no, args, fields);
+    }
 
-        let mut buf = Vec::<u8>::new();
-        let use_color = self.use_color_for_target(target);
+    /// Emit a log message tagged with several groups, shown in order (`[net][retry]`).
+    /// The first one picks the group level, sink and observer group like a single
+    /// group would; an empty slice logs without a group
+    /// # Panics
+    /// This function will panic if locking the format fails
+    pub fn emit_groups_to(
+        &self,
+        l: Level,
+        groups: &[&str],
+        module: &str,
+        file: &str,
+        line_no: u32,
+        args: Arguments,
+    ) {
+        let (group, extra) = split_groups(groups);
+        self.emit_record(l, group, extra, module, file, line_no, args, &[]);
+    }
 
-        if self.show_time.load(Ordering::Relaxed) {
-            write_timestamp(&mut buf);
+    /// [`Self::try_emit_record`] with write errors handed to `on_error`; returns
+    /// whether the record got past the level checks and the filter
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn emit_record(
+        &self,
+        l: Level,
+        group: Option<&str>,
+        extra: &[&str],
+        module: &str,
+        file: &str,
+        line_no: u32,
+        args: Arguments,
+        fields: &[(&str, &dyn core::fmt::Display)],
+    ) -> bool {
+        match self.try_emit_record(l, group, extra, module, file, line_no, None, args, fields) {
+            Ok(emitted) => emitted,
+            Err(e) => {
+                self.report_error(e);
+                true
+            }
         }
-        write_level(&mut buf, l, use_color);
+    }
+
+    /// Like [`Self::emit_to`], but returns the sink's write error instead of
+    /// handing it to the [`Self::set_on_error`] callback
+    /// # Errors
+    /// Returns the error from writing the line to the sink
+    /// # Panics
+    /// This function will panic if locking the format fails
+    pub fn try_emit_to(
+        &self,
+        l: Level,
+        group: Option<&str>,
+        file: &str,
+        line_no: u32,
+        args: Arguments,
+    ) -> io::Result<()> {
+        self.try_emit_kv_to(l, group, "", file, line_no, args, &[])
+    }
 
-        if self.show_tid.load(Ordering::Relaxed) {
-            #[cfg(feature = "thread-id")]
-            let _ = write!(&mut buf, " [{:?}]", std::thread::current().id());
+    /// Like [`Self::emit_kv_to`], but returns the sink's write error instead of
+    /// handing it to the [`Self::set_on_error`] callback
+    /// # Errors
+    /// Returns the error from writing the line to the sink
+    /// # Panics
+    /// This function will panic if locking the format fails
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_emit_kv_to(
+        &self,
+        l: Level,
+        group: Option<&str>,
+        module: &str,
+        file: &str,
+        line_no: u32,
+        args: Arguments,
+        fields: &[(&str, &dyn core::fmt::Display)],
+    ) -> io::Result<()> {
+        self.try_emit_record(l, group, &[], module, file, line_no, None, args, fields)
+            .map(drop)
+    }
+
+    /// Filter, render and write one record; `extra` holds the groups after `group`
+    /// and `msg`, if any, is the message text to copy instead of formatting `args`.
+    /// Returns `Ok(false)` if the level checks or the filter dropped it
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn try_emit_record(
+        &self,
+        l: Level,
+        group: Option<&str>,
+        extra: &[&str],
+        module: &str,
+        file: &str,
+        line_no: u32,
+        msg: Option<&str>,
+        args: Arguments,
+        fields: &[(&str, &dyn core::fmt::Display)],
+    ) -> io::Result<bool> {
+        if !ct_enabled(l) || !self.is_enabled() {
+            return Ok(false);
         }
-        if self.show_file_line.load(Ordering::Relaxed) {
-            let _ = write!(&mut buf, " <{file}:{line_no}>");
+        let layout = self.layout();
+        let layout = layout.as_deref().unwrap_or(&Layout::DEFAULT);
+        let group = group
+            .or_else(crate::context::current_group)
+            .or(layout.default_group);
+        if !self.level_passes(l, group) {
+            return Ok(false);
         }
 
-        if self.show_group.load(Ordering::Relaxed) {
-            if let Some(g) = group {
-                #[cfg(feature = "color")]
-                if use_color {
-                    let _ = write!(
-                        &mut buf,
-                        " [{}{}{}{}]",
-                        color::BOLD,
-                        level_color(l),
-                        g,
-                        color::RST
-                    );
-                } else {
-                    let _ = write!(&mut buf, " [{g}]");
-                }
-                #[cfg(not(feature = "color"))]
-                {
-                    let _ = write!(&mut buf, " [{g}]");
-                }
+        let sink = self.sink();
+        let color = self.use_color_for_target(l, sink.0.route(l));
+        let show_file = self.show_file_line.load(Ordering::Relaxed);
+        let show_time = cfg!(feature = "timestamp") && self.show_time.load(Ordering::Relaxed);
+        let time_source = self.time_source();
+        let thread = (cfg!(feature = "thread-id") && self.show_tid.load(Ordering::Relaxed))
+            .then(std::thread::current);
+        let mut res = Ok(false);
+        crate::context::with_context(|context| {
+            let mut record = Record {
+                level: l,
+                group,
+                extra_groups: extra,
+                file: Some(file),
+                file_link: (show_file && color && self.file_line_hyperlink()).then_some(file),
+                module: Some(module).filter(|m| !m.is_empty() && self.show_module()),
+                line: line_no,
+                msg,
+                args: &args,
+                timestamp: (show_time && time_source == TimeSource::Wall).then(crate::now),
+                uptime: (show_time && time_source == TimeSource::Uptime)
+                    .then(|| Duration::from_nanos(crate::mono_nanos())),
+                hostname: self
+                    .show_hostname()
+                    .then(crate::hostname)
+                    .filter(|h| !h.is_empty()),
+                pid: self.show_pid().then(std::process::id),
+                delta: None,
+                seq: None,
+                thread_id: thread.as_ref().map(std::thread::Thread::id),
+                thread_name: thread
+                    .as_ref()
+                    .filter(|_| self.thread_label() != ThreadLabel::Id)
+                    .and_then(std::thread::Thread::name),
+                context,
+                fields,
+                color,
+                level_color: layout.level_color(l),
+                sanitize: self.sanitize(),
+                max_message_len: self.max_message_len(),
+                field_separator: layout.field_sep,
+                group_delimiters: layout.group_delims,
+                eol: self.eol(),
+                level_style: self.level_style(),
+                color_scope: self.color_scope(),
+                group_color_mode: self.group_color_mode(),
+                group_bold: self.group_bold(),
+                dim_metadata: self.dim_metadata(),
+                timestamp_precision: self.timestamp_precision(),
+                timestamp_z_suffix: self.timestamp_z_suffix(),
+                timestamp_format: self.timestamp_format(),
+                indent: crate::context::indent_depth().saturating_mul(self.indent_width()),
+            };
+            if layout.filter.as_ref().is_some_and(|f| !f(&record)) {
+                return;
             }
-        }
-
-        let _ = buf.write_all(b" ");
-        let _ = buf.write_fmt(args);
-        let _ = buf.write_all(b"\n");
-
-        let _g = EMIT_LOCK.lock().unwrap();
-        match target {
-            Target::Stdout => {
-                let _ = io::stdout().lock().write_all(&buf);
+            let dest = layout
+                .router
+                .as_ref()
+                .and_then(|f| self.registered_sink(f(&record)));
+            if dest.is_some() {
+                record.color = self.use_color_for_target(l, Target::Writer);
+                record.file_link = record.file_link.filter(|_| record.color);
+            }
+            let sink = dest.map_or(sink, |w| (Target::Writer, Some(w)));
+            record.group = group.filter(|_| self.show_group.load(Ordering::Relaxed));
+            if record.group.is_none() {
+                record.extra_groups = &[];
             }
-            Target::Stderr => {
-                let _ = io::stderr().lock().write_all(&buf);
+            record.file = show_file.then(|| layout.path_style.apply(file));
+            if self.show_delta() {
+                record.delta = self.delta();
             }
-            Target::Writer => {
-                if let Some(w) = writer {
-                    let _ = w.lock().unwrap().write_all(&buf);
+            res = self
+                .write_record(sink, layout.format.as_deref(), &mut record)
+                .map(|()| true);
+            if res.is_ok() {
+                self.counts.0[l as usize].fetch_add(1, Ordering::Relaxed);
+            }
+            if let Some(f) = &layout.observer {
+                f(l, group);
+            }
+        });
+        res
+    }
+
+    /// Number, render and write a filtered record, directly or through the line
+    /// buffer
+    fn write_record(
+        &self,
+        sink: (Target, Option<SharedWriter>),
+        format: Option<&(dyn Format + Send + Sync)>,
+        record: &mut Record,
+    ) -> io::Result<()> {
+        let l = record.level;
+        let show_seq = self.show_seq();
+        if self.direct_write() {
+            return self.write_direct(l, sink, |w| {
+                if show_seq {
+                    record.seq = Some(self.next_seq());
                 }
+                crate::format::render_direct(w, format, record)
+            });
+        }
+        if show_seq {
+            record.seq = Some(self.next_seq());
+        }
+        let mut res = Ok(());
+        with_line_buf(|buf| {
+            crate::format::render(buf, format, record);
+            res = self.write_to(l, sink, buf);
+        });
+        res
+    }
+
+    /// Hand a write error to the `on_error` callback, if any
+    fn report_error(&self, e: io::Error) {
+        let Some(f) = self.on_error.read().unwrap().clone() else {
+            return;
+        };
+        let _ = IN_ON_ERROR.try_with(|busy| {
+            if !busy.replace(true) {
+                f(e);
+                busy.set(false);
             }
+        });
+    }
+
+    /// Write `bytes` as-is to `dest`, or else the sink a record at `l` is routed to
+    pub(crate) fn write_raw(
+        &self,
+        l: Level,
+        dest: Option<SharedWriter>,
+        bytes: &[u8],
+    ) -> io::Result<()> {
+        self.write_to(l, self.sink_or(dest), bytes)
+    }
+
+    /// Write `bytes` as-is to `sink`, as a record at `l` is routed
+    fn write_to(
+        &self,
+        l: Level,
+        (target, writer): (Target, Option<SharedWriter>),
+        bytes: &[u8],
+    ) -> io::Result<()> {
+        let _g = self.emit_lock.lock();
+        match target.route(l) {
+            Target::Stdout => io::stdout().lock().write_all(bytes),
+            // `route` never yields `Split`
+            Target::Stderr | Target::Split(_) => io::stderr().lock().write_all(bytes),
+            Target::Writer => writer.map_or(Ok(()), |w| w.lock().write_all(bytes)),
         }
     }
 
-    #[inline]
-    fn use_color_for_target(&self, target: Target) -> bool {
-        #[cfg(not(feature = "color"))]
-        {
-            return false;
+    /// Let `f` write a record in pieces to `sink`, as a record at `l` is routed,
+    /// holding the emit lock throughout
+    fn write_direct(
+        &self,
+        l: Level,
+        (target, writer): (Target, Option<SharedWriter>),
+        f: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+    ) -> io::Result<()> {
+        fn line_buffered<W: Write>(
+            w: W,
+            f: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+        ) -> io::Result<()> {
+            let mut lw = WholeLines {
+                inner: w,
+                line: Vec::new(),
+            };
+            f(&mut lw)?;
+            // hands over a partial last line without flushing the sink itself
+            if lw.line.is_empty() {
+                return Ok(());
+            }
+            lw.inner.write_all(&lw.line)
         }
-        #[cfg(feature = "color")]
-        match ColorMode::from(self.color_mode.load(Ordering::Relaxed)) {
-            ColorMode::Always => true,
-            ColorMode::Never => false,
-            ColorMode::Auto => match target {
-                Target::Stdout => io::stdout().is_terminal(),
-                Target::Stderr => io::stderr().is_terminal(),
-                Target::Writer => false,
-            },
+        let _g = self.emit_lock.lock();
+        match target.route(l) {
+            // already line buffered
+            Target::Stdout => f(&mut io::stdout().lock()),
+            Target::Stderr | Target::Split(_) => line_buffered(io::stderr().lock(), f),
+            Target::Writer => writer.map_or(Ok(()), |w| line_buffered(&mut **w.lock(), f)),
         }
     }
+
+    /// The current layout settings; `None` means all defaults
+    fn layout(&self) -> Option<Arc<Layout>> {
+        self.layout.read().unwrap().clone()
+    }
+
+    fn with_layout<T>(&self, f: impl FnOnce(&Layout) -> T) -> T {
+        let layout = self.layout.read().unwrap();
+        f(layout.as_deref().unwrap_or(&Layout::DEFAULT))
+    }
+
+    /// Swap in a copy of the layout settings changed by `f`, leaving records being
+    /// emitted with the ones they read
+    fn update_layout(&self, f: impl FnOnce(&mut Layout)) {
+        let mut cur = self.layout.write().unwrap();
+        let mut next = cur.as_deref().unwrap_or(&Layout::DEFAULT).clone();
+        f(&mut next);
+        *cur = Some(Arc::new(next));
+    }
+
+    fn sink(&self) -> (Target, Option<SharedWriter>) {
+        let s = self.sink.lock();
+        (s.target.unwrap_or(Target::Stderr), s.writer.clone())
+    }
+
+    /// A routed sink as a `Writer` target, else the regular one
+    fn sink_or(&self, dest: Option<SharedWriter>) -> (Target, Option<SharedWriter>) {
+        dest.map_or_else(|| self.sink(), |w| (Target::Writer, Some(w)))
+    }
+
+    #[inline]
+    fn use_color_for_target(&self, l: Level, target: Target) -> bool {
+        cfg!(feature = "color")
+            && match ColorMode::from(self.color_mode.load(Ordering::Relaxed)) {
+                _ if l < self.color_min_level() => false,
+                ColorMode::Always => true,
+                ColorMode::Never => false,
+                ColorMode::Auto => crate::auto_color(target),
+            }
+    }
 }
 
 /// Timer guard
 pub struct TimerGuard<'a> {
     logger: &'a Logger,
     label: &'static str,
+    level: Level,
+    min: Duration,
     start: Instant,
     file: &'static str,
     line: u32,
@@ -226,20 +1471,58 @@ impl<'a> TimerGuard<'a> {
     #[inline]
     #[must_use]
     pub fn new_at(logger: &'a Logger, label: &'static str, file: &'static str, line: u32) -> Self {
+        Self::new_at_min(logger, label, Duration::ZERO, file, line)
+    }
+    /// Create a new timer guard that only logs if the scope took at least `min`
+    #[inline]
+    #[must_use]
+    pub fn new_at_min(
+        logger: &'a Logger,
+        label: &'static str,
+        min: Duration,
+        file: &'static str,
+        line: u32,
+    ) -> Self {
         Self {
             logger,
             label,
+            level: Level::Info,
+            min,
             start: Instant::now(),
             file,
             line,
         }
     }
-}
-impl Drop for TimerGuard<'_> {
-    fn drop(&mut self) {
+    /// Set the level the timing is logged at (default `Info`)
+    #[inline]
+    #[must_use]
+    pub const fn with_level(mut self, l: Level) -> Self {
+        self.level = l;
+        self
+    }
+    /// Time elapsed so far
+    #[inline]
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+    /// Restart the timer; the logged time is measured from the last reset
+    #[inline]
+    pub fn reset(&mut self) {
+        self.start = Instant::now();
+    }
+    /// Log the timing now and return it
+    #[inline]
+    #[allow(clippy::must_use_candidate)]
+    pub fn finish(self) -> Duration {
         let elapsed = self.start.elapsed();
+        self.log(elapsed);
+        std::mem::forget(self);
+        elapsed
+    }
+    fn log(&self, elapsed: Duration) {
         self.logger.emit_to(
-            Level::Info,
+            self.level,
             Some(self.label),
             self.file,
             self.line,
@@ -247,9 +1530,35 @@ impl Drop for TimerGuard<'_> {
         );
     }
 }
+impl Drop for TimerGuard<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        if elapsed >= self.min {
+            self.log(elapsed);
+        }
+    }
+}
 #[macro_export]
 /// Macro for timing a scope
 macro_rules! __rustlog_local_scope_time {
+    ($lg:expr, $label:expr, min: $min:expr) => {
+        let _rustlog_scope_time_guard =
+            $crate::local::TimerGuard::new_at_min($lg, $label, $min, file!(), line!());
+    };
+    ($lg:expr, $label:expr, min: $min:expr, $body:block) => {{
+        let _rustlog_scope_time_guard =
+            $crate::local::TimerGuard::new_at_min($lg, $label, $min, file!(), line!());
+        $body
+    }};
+    ($lg:expr, level: $lvl:expr, $label:expr) => {
+        let _rustlog_scope_time_guard =
+            $crate::local::TimerGuard::new_at($lg, $label, file!(), line!()).with_level($lvl);
+    };
+    ($lg:expr, level: $lvl:expr, $label:expr, $body:block) => {{
+        let _rustlog_scope_time_guard =
+            $crate::local::TimerGuard::new_at($lg, $label, file!(), line!()).with_level($lvl);
+        $body
+    }};
     ($lg:expr, $label:expr) => {
         let _rustlog_scope_time_guard =
             $crate::local::TimerGuard::new_at($lg, $label, file!(), line!());
@@ -260,8 +1569,79 @@ macro_rules! __rustlog_local_scope_time {
         $body
     }};
 }
+#[macro_export]
+/// Macro for timing a block and returning `(value, Duration)`
+macro_rules! __rustlog_local_measure {
+    ($lg:expr, level: $lvl:expr, $label:expr, $body:block) => {{
+        let _rustlog_measure_guard =
+            $crate::local::TimerGuard::new_at($lg, $label, file!(), line!()).with_level($lvl);
+        let _rustlog_measure_value = $body;
+        (_rustlog_measure_value, _rustlog_measure_guard.finish())
+    }};
+    ($lg:expr, $label:expr, $body:block) => {
+        $crate::__rustlog_local_measure!($lg, level: $crate::Level::Info, $label, $body)
+    };
+}
 
 // Helper conversions if you keep enums repr(u8)
+impl From<u8> for TimeSource {
+    fn from(x: u8) -> Self {
+        match x {
+            1 => Self::Uptime,
+            _ => Self::Wall,
+        }
+    }
+}
+impl From<u8> for ThreadLabel {
+    fn from(x: u8) -> Self {
+        match x {
+            1 => Self::Name,
+            2 => Self::NameOrId,
+            _ => Self::Id,
+        }
+    }
+}
+impl From<u8> for TimestampFormat {
+    fn from(x: u8) -> Self {
+        match x {
+            1 => Self::Rfc3339,
+            _ => Self::SpaceSeparated,
+        }
+    }
+}
+impl From<u8> for LevelStyle {
+    fn from(x: u8) -> Self {
+        match x {
+            1 => Self::Short,
+            2 => Self::Lower,
+            _ => Self::Full,
+        }
+    }
+}
+impl From<u8> for ColorScope {
+    fn from(x: u8) -> Self {
+        match x {
+            1 => Self::WholeLine,
+            _ => Self::LevelOnly,
+        }
+    }
+}
+impl From<u8> for GroupColorMode {
+    fn from(x: u8) -> Self {
+        match x {
+            1 => Self::ByName,
+            _ => Self::ByLevel,
+        }
+    }
+}
+impl From<u8> for Eol {
+    fn from(x: u8) -> Self {
+        match x {
+            1 => Self::CrLf,
+            _ => Self::Lf,
+        }
+    }
+}
 impl From<u8> for ColorMode {
     fn from(x: u8) -> Self {
         match x {
@@ -276,43 +1656,221 @@ impl From<u8> for ColorMode {
 pub struct LoggerBuilder {
     level: Level,
     show_tid: Option<bool>,
+    thread_label: ThreadLabel,
+    time_source: TimeSource,
+    ts_precision: Option<Precision>,
+    ts_z_suffix: Option<bool>,
+    ts_format: Option<TimestampFormat>,
+    show_pid: Option<bool>,
+    show_hostname: Option<bool>,
+    show_delta: Option<bool>,
+    show_seq: Option<bool>,
+    direct_write: Option<bool>,
+    sanitize: Option<bool>,
+    max_message_len: Option<usize>,
     show_time: Option<bool>,
     show_group: Option<bool>,
     show_file_line: Option<bool>,
+    path_style: PathStyle,
+    show_module: Option<bool>,
     color_mode: Option<ColorMode>,
+    color_min_level: Option<Level>,
+    field_sep: Option<&'static str>,
+    group_delims: Option<(&'static str, &'static str)>,
+    eol: Option<Eol>,
+    level_style: Option<LevelStyle>,
+    color_scope: Option<ColorScope>,
+    group_color_mode: Option<GroupColorMode>,
+    group_bold: Option<bool>,
+    dim_metadata: Option<bool>,
+    indent_width: Option<usize>,
+    format: Option<Box<dyn Format + Send + Sync>>,
+    filter: Option<FilterFn>,
+    on_error: Option<ErrorFn>,
+    observer: Option<ObserverFn>,
+    level_colors: [Option<&'static str>; 7],
+    default_group: Option<&'static str>,
     target: Target,
-    writer: Option<Arc<StdMutex<Box<dyn Write + Send>>>>,
-    file_path: Option<std::path::PathBuf>,
+    writer: Option<SharedWriter>,
+    file: Option<FileSpec>,
+    panic_hook: bool,
+}
+
+/// File sink opened by `LoggerBuilder::build`
+enum FileSpec {
+    Plain(PathBuf, FileOptions),
+    Rotating {
+        path: PathBuf,
+        max_bytes: u64,
+        keep: usize,
+    },
+    Daily {
+        dir: PathBuf,
+        prefix: String,
+    },
+    #[cfg(feature = "gzip")]
+    Gzip(PathBuf),
+}
+
+impl FileSpec {
+    fn open(self) -> io::Result<Box<dyn Write + Send>> {
+        Ok(match self {
+            Self::Plain(p, opts) => Box::new(opts.open(p)?),
+            Self::Rotating {
+                path,
+                max_bytes,
+                keep,
+            } => Box::new(RotatingFile::new(path, max_bytes, keep)?),
+            Self::Daily { dir, prefix } => Box::new(DailyFile::new(dir, &prefix)?),
+            #[cfg(feature = "gzip")]
+            Self::Gzip(p) => Box::new(crate::sink::GzipFile::new(p)?),
+        })
+    }
 }
 impl Default for LoggerBuilder {
     fn default() -> Self {
         Self {
             level: Level::Info,
             show_tid: None,
+            thread_label: ThreadLabel::Id,
+            time_source: TimeSource::Wall,
+            ts_precision: None,
+            ts_z_suffix: None,
+            ts_format: None,
+            show_pid: None,
+            show_hostname: None,
+            show_delta: None,
+            show_seq: None,
+            direct_write: None,
+            sanitize: None,
+            max_message_len: None,
             show_time: None,
             show_group: None,
             show_file_line: None,
+            path_style: PathStyle::Full,
+            show_module: None,
             color_mode: None,
+            color_min_level: None,
+            field_sep: None,
+            group_delims: None,
+            eol: None,
+            level_style: None,
+            color_scope: None,
+            group_color_mode: None,
+            group_bold: None,
+            dim_metadata: None,
+            indent_width: None,
+            format: None,
+            filter: None,
+            on_error: None,
+            observer: None,
+            level_colors: [None; 7],
+            default_group: None,
             target: Target::Stderr,
             writer: None,
-            file_path: None,
+            file: None,
+            panic_hook: false,
         }
     }
-}
-
-impl LoggerBuilder {
+}
+
+impl LoggerBuilder {
+    #[inline]
+    #[must_use]
+    /// Set the log level
+    pub const fn set_level(mut self, l: Level) -> Self {
+        self.level = l;
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Show the thread id
+    pub const fn set_show_thread_id(mut self, on: bool) -> Self {
+        self.show_tid = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set how the thread is labelled
+    pub const fn set_thread_label(mut self, label: ThreadLabel) -> Self {
+        self.thread_label = label;
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Show the process id
+    pub const fn set_show_pid(mut self, on: bool) -> Self {
+        self.show_pid = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Show the hostname
+    pub const fn set_show_hostname(mut self, on: bool) -> Self {
+        self.show_hostname = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Show the time since the previous line
+    pub const fn set_show_delta(mut self, on: bool) -> Self {
+        self.show_delta = Some(on);
+        self
+    }
     #[inline]
     #[must_use]
-    /// Set the log level
-    pub const fn set_level(mut self, l: Level) -> Self {
-        self.level = l;
+    /// Number lines with a sequence number
+    pub const fn set_show_seq(mut self, on: bool) -> Self {
+        self.show_seq = Some(on);
         self
     }
     #[inline]
     #[must_use]
-    /// Show the thread id
-    pub const fn set_show_thread_id(mut self, on: bool) -> Self {
-        self.show_tid = Some(on);
+    /// Render records straight into the sink (see [`Logger::set_direct_write`])
+    pub const fn set_direct_write(mut self, on: bool) -> Self {
+        self.direct_write = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set what the timestamp shows
+    pub const fn set_time_source(mut self, src: TimeSource) -> Self {
+        self.time_source = src;
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the fractional second digits of wall-clock timestamps
+    pub const fn set_timestamp_precision(mut self, p: Precision) -> Self {
+        self.ts_precision = Some(p);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// End UTC timestamps with `Z`
+    pub const fn set_timestamp_z_suffix(mut self, on: bool) -> Self {
+        self.ts_z_suffix = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the layout of wall-clock timestamps
+    pub const fn set_timestamp_format(mut self, f: TimestampFormat) -> Self {
+        self.ts_format = Some(f);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Escape control characters in messages
+    pub const fn set_sanitize(mut self, on: bool) -> Self {
+        self.sanitize = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Cap messages at `max` bytes
+    pub const fn set_max_message_len(mut self, max: usize) -> Self {
+        self.max_message_len = Some(max);
         self
     }
     #[inline]
@@ -338,6 +1896,20 @@ impl LoggerBuilder {
     }
     #[inline]
     #[must_use]
+    /// Set how source paths are shown
+    pub const fn set_path_style(mut self, style: PathStyle) -> Self {
+        self.path_style = style;
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Show the module path of the call site
+    pub const fn set_show_module(mut self, on: bool) -> Self {
+        self.show_module = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
     /// Set the color mode
     pub const fn set_color_mode(mut self, m: ColorMode) -> Self {
         self.color_mode = Some(m);
@@ -345,6 +1917,161 @@ impl LoggerBuilder {
     }
     #[inline]
     #[must_use]
+    /// Color only records at `l` or above
+    pub const fn set_color_min_level(mut self, l: Level) -> Self {
+        self.color_min_level = Some(l);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the separator between prefix fields (see [`Logger::set_field_separator`])
+    pub const fn set_field_separator(mut self, sep: &'static str) -> Self {
+        self.field_sep = Some(sep);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the strings group tags are wrapped in (see [`Logger::set_group_delimiters`])
+    pub const fn set_group_delimiters(mut self, open: &'static str, close: &'static str) -> Self {
+        self.group_delims = Some((open, close));
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the line ending written after each record (see [`Logger::set_eol`])
+    pub const fn set_eol(mut self, e: Eol) -> Self {
+        self.eol = Some(e);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set how the default format renders levels
+    pub const fn set_level_style(mut self, s: LevelStyle) -> Self {
+        self.level_style = Some(s);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set how much of a line is colored when colors are on
+    pub const fn set_color_scope(mut self, s: ColorScope) -> Self {
+        self.color_scope = Some(s);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set how the `[group]` tag is colored when colors are on
+    pub const fn set_group_color_mode(mut self, m: GroupColorMode) -> Self {
+        self.group_color_mode = Some(m);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Make a colored `[group]` tag bold
+    pub const fn set_group_bold(mut self, on: bool) -> Self {
+        self.group_bold = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Render the timestamp, thread id and `<file:line>` faint
+    pub const fn set_dim_metadata(mut self, on: bool) -> Self {
+        self.dim_metadata = Some(on);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set how many spaces each nested `scope!` indents the message by
+    pub const fn set_indent_width(mut self, spaces: usize) -> Self {
+        self.indent_width = Some(spaces);
+        self
+    }
+    // Short aliases of the `set_*` methods
+    #[inline]
+    #[must_use]
+    /// Alias of [`Self::set_level`]
+    pub const fn level(self, l: Level) -> Self {
+        self.set_level(l)
+    }
+    #[inline]
+    #[must_use]
+    /// Alias of [`Self::set_show_thread_id`]
+    pub const fn show_thread_id(self, on: bool) -> Self {
+        self.set_show_thread_id(on)
+    }
+    #[inline]
+    #[must_use]
+    /// Alias of [`Self::set_show_time`]
+    pub const fn show_time(self, on: bool) -> Self {
+        self.set_show_time(on)
+    }
+    #[inline]
+    #[must_use]
+    /// Alias of [`Self::set_show_group`]
+    pub const fn show_group(self, on: bool) -> Self {
+        self.set_show_group(on)
+    }
+    #[inline]
+    #[must_use]
+    /// Alias of [`Self::set_show_file_line`]
+    pub const fn show_file_line(self, on: bool) -> Self {
+        self.set_show_file_line(on)
+    }
+    #[inline]
+    #[must_use]
+    /// Alias of [`Self::set_color_mode`]
+    pub const fn color_mode(self, m: ColorMode) -> Self {
+        self.set_color_mode(m)
+    }
+    #[inline]
+    #[must_use]
+    /// Alias of [`Self::set_writer`]
+    pub fn writer(self, w: Box<dyn Write + Send>) -> Self {
+        self.set_writer(w)
+    }
+    #[inline]
+    #[must_use]
+    /// Tag records logged without a group with `group`
+    pub const fn default_group(mut self, group: &'static str) -> Self {
+        self.default_group = Some(group);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the line format
+    pub fn set_format(mut self, f: Box<dyn Format + Send + Sync>) -> Self {
+        self.format = Some(f);
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Drop records for which `f` returns `false` (see [`Logger::set_filter`])
+    pub fn set_filter(mut self, f: Box<dyn Fn(&Record) -> bool + Send + Sync>) -> Self {
+        self.filter = Some(Arc::from(f));
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Call `f` when writing a record fails (see [`Logger::set_on_error`])
+    pub fn set_on_error(mut self, f: Box<dyn Fn(io::Error) + Send + Sync>) -> Self {
+        self.on_error = Some(Arc::from(f));
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Call `f` for every emitted record (see [`Logger::set_observer`])
+    pub fn set_observer(mut self, f: Observer) -> Self {
+        self.observer = Some(Arc::from(f));
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Override the ANSI escape used for a level (a full escape like `"\x1b[38;5;208m"`)
+    pub const fn set_level_color(mut self, l: Level, ansi: &'static str) -> Self {
+        self.level_colors[l as usize] = Some(ansi);
+        self
+    }
+    #[inline]
+    #[must_use]
     /// Set the output target to stdout
     pub const fn stdout(mut self) -> Self {
         self.target = Target::Stdout;
@@ -362,45 +2089,125 @@ impl LoggerBuilder {
     /// Set the output target to a custom writer
     pub fn set_writer(mut self, w: Box<dyn Write + Send>) -> Self {
         self.target = Target::Writer;
-        self.writer = Some(Arc::new(StdMutex::new(w)));
+        self.writer = Some(Arc::new(Mutex::new(w)));
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Discard all output (see [`crate::sink::NullWriter`])
+    pub fn null(self) -> Self {
+        self.set_writer(Box::new(crate::sink::NullWriter))
+    }
+    #[inline]
+    #[must_use]
+    /// Set the output target to a file (appending)
+    pub fn file(self, p: impl AsRef<Path>) -> Self {
+        self.file_with(p, FileOptions::default())
+    }
+    #[inline]
+    #[must_use]
+    /// Set the output target to a file opened with `opts`
+    pub fn file_with(mut self, p: impl AsRef<Path>, opts: FileOptions) -> Self {
+        self.target = Target::Writer;
+        self.file = Some(FileSpec::Plain(p.as_ref().to_owned(), opts));
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the output target to a size-rotated file (see [`RotatingFile`])
+    pub fn rotating_file(mut self, p: impl AsRef<Path>, max_bytes: u64, keep: usize) -> Self {
+        self.target = Target::Writer;
+        self.file = Some(FileSpec::Rotating {
+            path: p.as_ref().to_owned(),
+            max_bytes,
+            keep,
+        });
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Set the output target to a file in `dir` rolled daily at UTC midnight (see [`DailyFile`])
+    pub fn daily_file(mut self, dir: impl AsRef<Path>, prefix: &str) -> Self {
+        self.target = Target::Writer;
+        self.file = Some(FileSpec::Daily {
+            dir: dir.as_ref().to_owned(),
+            prefix: prefix.to_owned(),
+        });
         self
     }
     #[inline]
     #[must_use]
-    /// Set the output target to a file
-    pub fn file(mut self, p: impl AsRef<std::path::Path>) -> Self {
+    /// Set the output target to a gzip-compressed file (see [`crate::sink::GzipFile`])
+    #[cfg(feature = "gzip")]
+    pub fn gzip_file(mut self, p: impl AsRef<Path>) -> Self {
         self.target = Target::Writer;
-        self.file_path = Some(p.as_ref().to_owned());
+        self.file = Some(FileSpec::Gzip(p.as_ref().to_owned()));
+        self
+    }
+    #[inline]
+    #[must_use]
+    /// Install the panic hook (see [`crate::install_panic_hook`]) in
+    /// [`install_global`](Self::install_global); ignored by `build`
+    pub const fn with_panic_hook(mut self, on: bool) -> Self {
+        self.panic_hook = on;
         self
     }
 
     /// Build the logger
     /// # Errors
     /// This function will return an error if the file cannot be opened for writing
-    pub fn build(self) -> io::Result<Logger> {
-        let writer = match (self.target, self.file_path) {
-            (Target::Writer, Some(p)) => {
-                let f = std::fs::OpenOptions::new()
-                    .create(true)
-                    .append(true)
-                    .open(p)?;
-                Some(Arc::new(
-                    StdMutex::new(Box::new(f) as Box<dyn Write + Send>),
-                ))
-            }
-            _ => self.writer,
+    pub fn build(mut self) -> io::Result<Logger> {
+        let writer = match (self.target, self.file.take()) {
+            (Target::Writer, Some(spec)) => Some(Arc::new(Mutex::new(spec.open()?))),
+            _ => self.writer.take(),
         };
         let lg = Logger {
-            sink: StdMutex::new(Sink {
-                target: self.target,
+            sink: Mutex::new(Sink {
+                target: Some(self.target),
                 writer,
             }),
+            layout: RwLock::new(Some(Arc::new(Layout {
+                filter: self.filter.take(),
+                observer: self.observer.take(),
+                ..Layout::DEFAULT
+            }))),
+            on_error: RwLock::new(self.on_error.take()),
             ..Logger::default()
         };
         lg.set_level(self.level);
         if let Some(x) = self.show_tid {
             lg.set_show_thread_id(x);
         }
+        lg.set_thread_label(self.thread_label);
+        lg.set_time_source(self.time_source);
+        if let Some(x) = self.ts_precision {
+            lg.set_timestamp_precision(x);
+        }
+        if let Some(x) = self.ts_z_suffix {
+            lg.set_timestamp_z_suffix(x);
+        }
+        if let Some(x) = self.ts_format {
+            lg.set_timestamp_format(x);
+        }
+        if let Some(x) = self.show_pid {
+            lg.set_show_pid(x);
+        }
+        if let Some(x) = self.show_hostname {
+            lg.set_show_hostname(x);
+        }
+        if let Some(x) = self.show_delta {
+            lg.set_show_delta(x);
+        }
+        if let Some(x) = self.show_seq {
+            lg.set_show_seq(x);
+        }
+        if let Some(x) = self.direct_write {
+            lg.set_direct_write(x);
+        }
+        if let Some(x) = self.sanitize {
+            lg.set_sanitize(x);
+        }
+        lg.set_max_message_len(self.max_message_len);
         if let Some(x) = self.show_time {
             lg.set_show_time(x);
         }
@@ -410,18 +2217,86 @@ impl LoggerBuilder {
         if let Some(x) = self.show_file_line {
             lg.set_show_file_line(x);
         }
+        lg.set_path_style(self.path_style);
+        if let Some(x) = self.show_module {
+            lg.set_show_module(x);
+        }
         if let Some(x) = self.color_mode {
             lg.set_color_mode(x);
         }
+        if let Some(x) = self.color_min_level {
+            lg.set_color_min_level(x);
+        }
+        self.apply_layout(&lg);
+        if let Some(x) = self.format.take() {
+            lg.set_format(x);
+        }
+        lg.set_default_group(self.default_group);
+        for (l, ansi) in Level::all().into_iter().zip(self.level_colors) {
+            if let Some(ansi) = ansi {
+                lg.set_level_color(l, ansi);
+            }
+        }
         Ok(lg)
     }
 
+    /// Apply the settings that only shape the default format's lines
+    fn apply_layout(&self, lg: &Logger) {
+        if let Some(x) = self.field_sep {
+            lg.set_field_separator(x);
+        }
+        if let Some((open, close)) = self.group_delims {
+            lg.set_group_delimiters(open, close);
+        }
+        if let Some(x) = self.eol {
+            lg.set_eol(x);
+        }
+        if let Some(x) = self.level_style {
+            lg.set_level_style(x);
+        }
+        if let Some(x) = self.color_scope {
+            lg.set_color_scope(x);
+        }
+        if let Some(x) = self.group_color_mode {
+            lg.set_group_color_mode(x);
+        }
+        if let Some(x) = self.group_bold {
+            lg.set_group_bold(x);
+        }
+        if let Some(x) = self.dim_metadata {
+            lg.set_dim_metadata(x);
+        }
+        if let Some(x) = self.indent_width {
+            lg.set_indent_width(x);
+        }
+    }
+
     /// Build the logger and leak it
     /// # Errors
     /// This function will return an error if the file cannot be opened for writing
     pub fn build_static(self) -> io::Result<&'static Logger> {
         Ok(Box::leak(Box::new(self.build()?)))
     }
+
+    /// Build the logger and make it the configuration behind the root macros
+    /// (`rustlog::info!`, ...) and the global free functions
+    ///
+    /// Everything the builder sets replaces the global logger's settings and sink;
+    /// per-group levels already set on it are kept. Installs the panic hook when
+    /// [`with_panic_hook`](Self::with_panic_hook) was set.
+    /// # Errors
+    /// This function will return an error if the file cannot be opened for writing;
+    /// the global logger is left unchanged then
+    /// # Panics
+    /// This function will panic if locking the global logger's settings fails
+    pub fn install_global(self) -> io::Result<()> {
+        let panic_hook = self.panic_hook;
+        crate::global().assign_from(&self.build()?);
+        if panic_hook {
+            crate::install_panic_hook();
+        }
+        Ok(())
+    }
 }
 
 // ===== Macros (require a logger argument) ====================================
@@ -434,9 +2309,151 @@ impl LoggerBuilder {
 macro_rules! __rustlog_local_log {
     ($lg:expr, $lvl:expr, $grp:expr, $($t:tt)+) => {{
         let __lg = $lg; // evaluate once
-        if $crate::ct_enabled($lvl) { __lg.emit_to($lvl, $grp, file!(), line!(), format_args!($($t)+)); }
+        if $crate::ct_enabled($lvl) { __lg.emit_kv_to($lvl, $grp, module_path!(), file!(), line!(), format_args!($($t)+), &[]); }
+    }}
+}
+
+#[macro_export]
+/// Emit a log message tagged with several groups
+macro_rules! __rustlog_local_log_groups {
+    ($lg:expr, $lvl:expr, $grps:expr, $($t:tt)+) => {{
+        let __lg = $lg; // evaluate once
+        if $crate::ct_enabled($lvl) { __lg.emit_groups_to($lvl, $grps, module_path!(), file!(), line!(), format_args!($($t)+)); }
+    }}
+}
+
+#[macro_export]
+/// Emit a log message with trailing `key = value` fields
+macro_rules! __rustlog_local_log_kv {
+    ($lg:expr, $lvl:expr, $grp:expr, $msg:literal $(, $k:ident = $v:expr)* $(,)?) => {{
+        let __lg = $lg; // evaluate once
+        if $crate::ct_enabled($lvl) {
+            __lg.emit_kv_to($lvl, $grp, module_path!(), file!(), line!(), format_args!($msg), &[$((stringify!($k), &$v as &dyn ::core::fmt::Display)),*]);
+        }
+    }}
+}
+#[macro_export]
+/// Emit a trace log message with fields
+macro_rules! __rustlog_local_trace_kv { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_kv!($lg, $crate::Level::Trace, None, $($t)+) } }
+#[macro_export]
+/// Emit a debug log message with fields
+macro_rules! __rustlog_local_debug_kv { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_kv!($lg, $crate::Level::Debug, None, $($t)+) } }
+#[macro_export]
+/// Emit an info log message with fields
+macro_rules! __rustlog_local_info_kv  { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_kv!($lg, $crate::Level::Info,  None, $($t)+) } }
+#[macro_export]
+/// Emit a notice log message with fields
+macro_rules! __rustlog_local_notice_kv { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_kv!($lg, $crate::Level::Notice, None, $($t)+) } }
+#[macro_export]
+/// Emit a warn log message with fields
+macro_rules! __rustlog_local_warn_kv  { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_kv!($lg, $crate::Level::Warn,  None, $($t)+) } }
+#[macro_export]
+/// Emit an error log message with fields
+macro_rules! __rustlog_local_error_kv { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_kv!($lg, $crate::Level::Error, None, $($t)+) } }
+#[macro_export]
+/// Emit a fatal log message with fields
+macro_rules! __rustlog_local_fatal_kv { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_kv!($lg, $crate::Level::Fatal, None, $($t)+) } }
+
+#[macro_export]
+/// Emit a log message at most once per call site (shared by all loggers)
+macro_rules! __rustlog_local_log_once {
+    ($lg:expr, $lvl:expr, $grp:expr, $($t:tt)+) => {{
+        static ONCE: ::core::sync::atomic::AtomicBool = ::core::sync::atomic::AtomicBool::new(false);
+        let __lg = $lg; // evaluate once
+        if $crate::ct_enabled($lvl) && __lg.enabled($lvl, $grp) && !ONCE.swap(true, ::core::sync::atomic::Ordering::Relaxed) {
+            __lg.emit_kv_to($lvl, $grp, module_path!(), file!(), line!(), format_args!($($t)+), &[]);
+        }
+    }}
+}
+#[macro_export]
+/// Emit a trace log message once per call site
+macro_rules! __rustlog_local_trace_once { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_once!($lg, $crate::Level::Trace, None, $($t)+) } }
+#[macro_export]
+/// Emit a debug log message once per call site
+macro_rules! __rustlog_local_debug_once { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_once!($lg, $crate::Level::Debug, None, $($t)+) } }
+#[macro_export]
+/// Emit an info log message once per call site
+macro_rules! __rustlog_local_info_once  { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_once!($lg, $crate::Level::Info,  None, $($t)+) } }
+#[macro_export]
+/// Emit a notice log message once per call site
+macro_rules! __rustlog_local_notice_once { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_once!($lg, $crate::Level::Notice, None, $($t)+) } }
+#[macro_export]
+/// Emit a warn log message once per call site
+macro_rules! __rustlog_local_warn_once  { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_once!($lg, $crate::Level::Warn,  None, $($t)+) } }
+#[macro_export]
+/// Emit an error log message once per call site
+macro_rules! __rustlog_local_error_once { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_once!($lg, $crate::Level::Error, None, $($t)+) } }
+#[macro_export]
+/// Emit a fatal log message once per call site
+macro_rules! __rustlog_local_fatal_once { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log_once!($lg, $crate::Level::Fatal, None, $($t)+) } }
+
+#[macro_export]
+/// Emit a log message on the 1st, (n+1)th, (2n+1)th, ... call at this call site (shared by all loggers)
+macro_rules! __rustlog_local_log_every_n {
+    ($lg:expr, $n:expr, $lvl:expr, $grp:expr, $($t:tt)+) => {{
+        static COUNT: ::core::sync::atomic::AtomicUsize = ::core::sync::atomic::AtomicUsize::new(0);
+        let __lg = $lg; // evaluate once
+        if $crate::ct_enabled($lvl)
+            && __lg.enabled($lvl, $grp)
+            && COUNT.fetch_add(1, ::core::sync::atomic::Ordering::Relaxed) % ::core::cmp::max($n, 1usize) == 0
+        {
+            __lg.emit_kv_to($lvl, $grp, module_path!(), file!(), line!(), format_args!($($t)+), &[]);
+        }
+    }}
+}
+#[macro_export]
+/// Emit a trace log message every n-th call per call site
+macro_rules! __rustlog_local_trace_every_n { ($lg:expr, $n:expr, $($t:tt)+) => { $crate::__rustlog_local_log_every_n!($lg, $n, $crate::Level::Trace, None, $($t)+) } }
+#[macro_export]
+/// Emit a debug log message every n-th call per call site
+macro_rules! __rustlog_local_debug_every_n { ($lg:expr, $n:expr, $($t:tt)+) => { $crate::__rustlog_local_log_every_n!($lg, $n, $crate::Level::Debug, None, $($t)+) } }
+#[macro_export]
+/// Emit an info log message every n-th call per call site
+macro_rules! __rustlog_local_info_every_n  { ($lg:expr, $n:expr, $($t:tt)+) => { $crate::__rustlog_local_log_every_n!($lg, $n, $crate::Level::Info,  None, $($t)+) } }
+#[macro_export]
+/// Emit a notice log message every n-th call per call site
+macro_rules! __rustlog_local_notice_every_n { ($lg:expr, $n:expr, $($t:tt)+) => { $crate::__rustlog_local_log_every_n!($lg, $n, $crate::Level::Notice, None, $($t)+) } }
+#[macro_export]
+/// Emit a warn log message every n-th call per call site
+macro_rules! __rustlog_local_warn_every_n  { ($lg:expr, $n:expr, $($t:tt)+) => { $crate::__rustlog_local_log_every_n!($lg, $n, $crate::Level::Warn,  None, $($t)+) } }
+#[macro_export]
+/// Emit an error log message every n-th call per call site
+macro_rules! __rustlog_local_error_every_n { ($lg:expr, $n:expr, $($t:tt)+) => { $crate::__rustlog_local_log_every_n!($lg, $n, $crate::Level::Error, None, $($t)+) } }
+#[macro_export]
+/// Emit a fatal log message every n-th call per call site
+macro_rules! __rustlog_local_fatal_every_n { ($lg:expr, $n:expr, $($t:tt)+) => { $crate::__rustlog_local_log_every_n!($lg, $n, $crate::Level::Fatal, None, $($t)+) } }
+
+#[macro_export]
+/// Emit a log message only if `cond` holds; the condition is checked first, so neither
+/// the logger nor the arguments are evaluated when it is false
+macro_rules! __rustlog_local_log_if {
+    ($lg:expr, $cond:expr, $lvl:expr, $grp:expr, $($t:tt)+) => {{
+        if $cond {
+            $crate::__rustlog_local_log!($lg, $lvl, $grp, $($t)+);
+        }
     }}
 }
+#[macro_export]
+/// Emit a trace log message only if the condition holds
+macro_rules! __rustlog_local_trace_if { ($lg:expr, $cond:expr, $($t:tt)+) => { $crate::__rustlog_local_log_if!($lg, $cond, $crate::Level::Trace, None, $($t)+) } }
+#[macro_export]
+/// Emit a debug log message only if the condition holds
+macro_rules! __rustlog_local_debug_if { ($lg:expr, $cond:expr, $($t:tt)+) => { $crate::__rustlog_local_log_if!($lg, $cond, $crate::Level::Debug, None, $($t)+) } }
+#[macro_export]
+/// Emit an info log message only if the condition holds
+macro_rules! __rustlog_local_info_if  { ($lg:expr, $cond:expr, $($t:tt)+) => { $crate::__rustlog_local_log_if!($lg, $cond, $crate::Level::Info,  None, $($t)+) } }
+#[macro_export]
+/// Emit a notice log message only if the condition holds
+macro_rules! __rustlog_local_notice_if { ($lg:expr, $cond:expr, $($t:tt)+) => { $crate::__rustlog_local_log_if!($lg, $cond, $crate::Level::Notice, None, $($t)+) } }
+#[macro_export]
+/// Emit a warn log message only if the condition holds
+macro_rules! __rustlog_local_warn_if  { ($lg:expr, $cond:expr, $($t:tt)+) => { $crate::__rustlog_local_log_if!($lg, $cond, $crate::Level::Warn,  None, $($t)+) } }
+#[macro_export]
+/// Emit an error log message only if the condition holds
+macro_rules! __rustlog_local_error_if { ($lg:expr, $cond:expr, $($t:tt)+) => { $crate::__rustlog_local_log_if!($lg, $cond, $crate::Level::Error, None, $($t)+) } }
+#[macro_export]
+/// Emit a fatal log message only if the condition holds
+macro_rules! __rustlog_local_fatal_if { ($lg:expr, $cond:expr, $($t:tt)+) => { $crate::__rustlog_local_log_if!($lg, $cond, $crate::Level::Fatal, None, $($t)+) } }
 
 #[macro_export]
 /// Emit a trace log message
@@ -448,6 +2465,9 @@ macro_rules! __rustlog_local_debug { ($lg:expr, $($t:tt)+) => { $crate::__rustlo
 /// Emit an info log message
 macro_rules! __rustlog_local_info  { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log!($lg, $crate::Level::Info,  None, $($t)+) } }
 #[macro_export]
+/// Emit a notice log message
+macro_rules! __rustlog_local_notice { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log!($lg, $crate::Level::Notice, None, $($t)+) } }
+#[macro_export]
 /// Emit a warning log message
 macro_rules! __rustlog_local_warn  { ($lg:expr, $($t:tt)+) => { $crate::__rustlog_local_log!($lg, $crate::Level::Warn,  None, $($t)+) } }
 #[macro_export]
@@ -467,6 +2487,9 @@ macro_rules! __rustlog_local_debug_group { ($lg:expr, $grp:expr, $($t:tt)+) => {
 /// Emit an info group
 macro_rules! __rustlog_local_info_group  { ($lg:expr, $grp:expr, $($t:tt)+) => { $crate::__rustlog_local_log!($lg, $crate::Level::Info,  Some($grp), $($t)+) } }
 #[macro_export]
+/// Emit a notice group
+macro_rules! __rustlog_local_notice_group { ($lg:expr, $grp:expr, $($t:tt)+) => { $crate::__rustlog_local_log!($lg, $crate::Level::Notice, Some($grp), $($t)+) } }
+#[macro_export]
 /// Emit a warning group
 macro_rules! __rustlog_local_warn_group  { ($lg:expr, $grp:expr, $($t:tt)+) => { $crate::__rustlog_local_log!($lg, $crate::Level::Warn,  Some($grp), $($t)+) } }
 #[macro_export]
@@ -476,6 +2499,28 @@ macro_rules! __rustlog_local_error_group { ($lg:expr, $grp:expr, $($t:tt)+) => {
 /// Emit a fatal group
 macro_rules! __rustlog_local_fatal_group { ($lg:expr, $grp:expr, $($t:tt)+) => { $crate::__rustlog_local_log!($lg, $crate::Level::Fatal, Some($grp), $($t)+) } }
 
+#[macro_export]
+/// Emit a trace message with several groups
+macro_rules! __rustlog_local_trace_groups { ($lg:expr, $grps:expr, $($t:tt)+) => { $crate::__rustlog_local_log_groups!($lg, $crate::Level::Trace, $grps, $($t)+) } }
+#[macro_export]
+/// Emit a debug message with several groups
+macro_rules! __rustlog_local_debug_groups { ($lg:expr, $grps:expr, $($t:tt)+) => { $crate::__rustlog_local_log_groups!($lg, $crate::Level::Debug, $grps, $($t)+) } }
+#[macro_export]
+/// Emit an info message with several groups
+macro_rules! __rustlog_local_info_groups  { ($lg:expr, $grps:expr, $($t:tt)+) => { $crate::__rustlog_local_log_groups!($lg, $crate::Level::Info,  $grps, $($t)+) } }
+#[macro_export]
+/// Emit a notice message with several groups
+macro_rules! __rustlog_local_notice_groups { ($lg:expr, $grps:expr, $($t:tt)+) => { $crate::__rustlog_local_log_groups!($lg, $crate::Level::Notice, $grps, $($t)+) } }
+#[macro_export]
+/// Emit a warning message with several groups
+macro_rules! __rustlog_local_warn_groups  { ($lg:expr, $grps:expr, $($t:tt)+) => { $crate::__rustlog_local_log_groups!($lg, $crate::Level::Warn,  $grps, $($t)+) } }
+#[macro_export]
+/// Emit an error message with several groups
+macro_rules! __rustlog_local_error_groups { ($lg:expr, $grps:expr, $($t:tt)+) => { $crate::__rustlog_local_log_groups!($lg, $crate::Level::Error, $grps, $($t)+) } }
+#[macro_export]
+/// Emit a fatal message with several groups
+macro_rules! __rustlog_local_fatal_groups { ($lg:expr, $grps:expr, $($t:tt)+) => { $crate::__rustlog_local_log_groups!($lg, $crate::Level::Fatal, $grps, $($t)+) } }
+
 // Re-export ergonomic names under `rustlog::local`.
 // Import style: `use rustlog::local::info; info!(logger, "...");`
 // (Note: macro re-export keeps them callable after `use`; absolute path calling
@@ -484,6 +2529,7 @@ pub use crate::__rustlog_local_debug as debug;
 pub use crate::__rustlog_local_error as error;
 pub use crate::__rustlog_local_fatal as fatal;
 pub use crate::__rustlog_local_info as info;
+pub use crate::__rustlog_local_notice as notice;
 pub use crate::__rustlog_local_trace as trace;
 pub use crate::__rustlog_local_warn as warn;
 
@@ -491,7 +2537,49 @@ pub use crate::__rustlog_local_debug_group as debug_group;
 pub use crate::__rustlog_local_error_group as error_group;
 pub use crate::__rustlog_local_fatal_group as fatal_group;
 pub use crate::__rustlog_local_info_group as info_group;
+pub use crate::__rustlog_local_notice_group as notice_group;
 pub use crate::__rustlog_local_trace_group as trace_group;
 pub use crate::__rustlog_local_warn_group as warn_group;
 
+pub use crate::__rustlog_local_debug_groups as debug_groups;
+pub use crate::__rustlog_local_error_groups as error_groups;
+pub use crate::__rustlog_local_fatal_groups as fatal_groups;
+pub use crate::__rustlog_local_info_groups as info_groups;
+pub use crate::__rustlog_local_notice_groups as notice_groups;
+pub use crate::__rustlog_local_trace_groups as trace_groups;
+pub use crate::__rustlog_local_warn_groups as warn_groups;
+
+pub use crate::__rustlog_local_debug_once as debug_once;
+pub use crate::__rustlog_local_error_once as error_once;
+pub use crate::__rustlog_local_fatal_once as fatal_once;
+pub use crate::__rustlog_local_info_once as info_once;
+pub use crate::__rustlog_local_notice_once as notice_once;
+pub use crate::__rustlog_local_trace_once as trace_once;
+pub use crate::__rustlog_local_warn_once as warn_once;
+
+pub use crate::__rustlog_local_debug_every_n as debug_every_n;
+pub use crate::__rustlog_local_error_every_n as error_every_n;
+pub use crate::__rustlog_local_fatal_every_n as fatal_every_n;
+pub use crate::__rustlog_local_info_every_n as info_every_n;
+pub use crate::__rustlog_local_notice_every_n as notice_every_n;
+pub use crate::__rustlog_local_trace_every_n as trace_every_n;
+pub use crate::__rustlog_local_warn_every_n as warn_every_n;
+
+pub use crate::__rustlog_local_debug_kv as debug_kv;
+pub use crate::__rustlog_local_error_kv as error_kv;
+pub use crate::__rustlog_local_fatal_kv as fatal_kv;
+pub use crate::__rustlog_local_info_kv as info_kv;
+pub use crate::__rustlog_local_notice_kv as notice_kv;
+pub use crate::__rustlog_local_trace_kv as trace_kv;
+pub use crate::__rustlog_local_warn_kv as warn_kv;
+
+pub use crate::__rustlog_local_debug_if as debug_if;
+pub use crate::__rustlog_local_error_if as error_if;
+pub use crate::__rustlog_local_fatal_if as fatal_if;
+pub use crate::__rustlog_local_info_if as info_if;
+pub use crate::__rustlog_local_notice_if as notice_if;
+pub use crate::__rustlog_local_trace_if as trace_if;
+pub use crate::__rustlog_local_warn_if as warn_if;
+
+pub use crate::__rustlog_local_measure as measure;
 pub use crate::__rustlog_local_scope_time as scope_time;
@@ -0,0 +1,51 @@
+use crate::Level;
+
+/// A `log::Log` implementation that forwards records to the global logger.
+///
+/// The record's target becomes the group, so `RUST_LOG`-style group levels
+/// (`hyper=warn`) apply to dependencies too.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct LogBridge;
+
+const fn level(l: log::Level) -> Level {
+    match l {
+        log::Level::Trace => Level::Trace,
+        log::Level::Debug => Level::Debug,
+        log::Level::Info => Level::Info,
+        log::Level::Warn => Level::Warn,
+        log::Level::Error => Level::Error,
+    }
+}
+
+impl log::Log for LogBridge {
+    fn enabled(&self, m: &log::Metadata) -> bool {
+        crate::enabled(level(m.level()), Some(m.target()))
+    }
+    fn log(&self, r: &log::Record) {
+        if self.enabled(r.metadata()) {
+            crate::emit_kv(
+                level(r.level()),
+                Some(r.target()),
+                r.module_path().unwrap_or(""),
+                r.file().unwrap_or("<unknown>"),
+                r.line().unwrap_or(0),
+                *r.args(),
+                &[],
+            );
+        }
+    }
+    fn flush(&self) {
+        let _ = crate::flush();
+    }
+}
+
+/// Installs [`LogBridge`] as the `log` crate's logger.
+///
+/// `log`'s max level is set to `Trace` so rustlog's own level and group rules decide.
+/// # Errors
+/// Returns an error if a `log` logger was already installed
+pub fn init_log_bridge() -> Result<(), log::SetLoggerError> {
+    log::set_logger(&LogBridge)?;
+    log::set_max_level(log::LevelFilter::Trace);
+    Ok(())
+}
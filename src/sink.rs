@@ -0,0 +1,639 @@
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::Clock;
+
+/// Writer that fans out every write to several inner writers.
+///
+/// All writers receive the data even if one of them fails; the first error is returned.
+pub struct MultiWriter {
+    writers: Vec<Box<dyn Write + Send>>,
+}
+
+impl MultiWriter {
+    /// Create a new `MultiWriter`
+    #[inline]
+    #[must_use]
+    pub fn new(writers: Vec<Box<dyn Write + Send>>) -> Self {
+        Self { writers }
+    }
+    /// Add another writer
+    #[inline]
+    pub fn push(&mut self, w: Box<dyn Write + Send>) {
+        self.writers.push(w);
+    }
+    /// Number of inner writers
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.writers.len()
+    }
+    /// Returns `true` if there are no inner writers
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.writers.is_empty()
+    }
+
+    fn for_each(&mut self, mut f: impl FnMut(&mut dyn Write) -> io::Result<()>) -> io::Result<()> {
+        let mut first_err = None;
+        for w in &mut self.writers {
+            if let Err(e) = f(w.as_mut()) {
+                first_err.get_or_insert(e);
+            }
+        }
+        first_err.map_or(Ok(()), Err)
+    }
+}
+
+impl Write for MultiWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.for_each(|w| w.write_all(buf))
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.for_each(|w| w.flush())
+    }
+}
+
+/// Duplicate output into two writers
+#[inline]
+#[must_use]
+pub fn tee(a: Box<dyn Write + Send>, b: Box<dyn Write + Send>) -> MultiWriter {
+    MultiWriter::new(vec![a, b])
+}
+
+/// Writer that discards everything, for measuring formatting cost or running
+/// quietly while still exercising the formatting code.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NullWriter;
+
+impl Write for NullWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(buf.len())
+    }
+    #[inline]
+    fn write_all(&mut self, _: &[u8]) -> io::Result<()> {
+        Ok(())
+    }
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// File that rolls over by size.
+///
+/// When a write would push the file past `max_bytes`, `app.log` is renamed to
+/// `app.log.1` (older files shift up to `app.log.N`, the oldest is deleted) and a
+/// fresh `app.log` is opened. Each write lands in a single file, so a log line
+/// is never split across two files.
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    /// Open (or create) `path` for appending, keeping at most `keep` rotated files
+    /// # Errors
+    /// This function will return an error if the file cannot be opened for writing
+    pub fn new(path: impl AsRef<Path>, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_owned();
+        let file = open_append(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            max_bytes,
+            keep,
+            file,
+            size,
+        })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut p = self.path.clone().into_os_string();
+        p.push(format!(".{n}"));
+        p.into()
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.keep == 0 {
+            ignore_not_found(fs::remove_file(&self.path))?;
+        } else {
+            ignore_not_found(fs::remove_file(self.rotated(self.keep)))?;
+            for n in (1..self.keep).rev() {
+                ignore_not_found(fs::rename(self.rotated(n), self.rotated(n + 1)))?;
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len() as u64;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(buf)?;
+        self.size += len;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// File that rolls over at UTC midnight, named `<prefix>-YYYY-MM-DD.log`.
+///
+/// The date is checked on every write; the first write of a new day opens a new
+/// file. With [`DailyFile::keep`], files older than the last N days are deleted.
+pub struct DailyFile {
+    dir: PathBuf,
+    prefix: String,
+    keep: Option<usize>,
+    // `None` follows the global clock (see `set_clock`)
+    clock: Option<Box<dyn Clock + Send>>,
+    day: i64,
+    file: File,
+}
+
+impl DailyFile {
+    /// Open (or create) today's file in `dir`
+    /// # Errors
+    /// This function will return an error if the file cannot be opened for writing
+    pub fn new(dir: impl AsRef<Path>, prefix: &str) -> io::Result<Self> {
+        Self::open(dir.as_ref(), prefix, None)
+    }
+    /// Like [`DailyFile::new`], but reads the current time from `clock`
+    /// # Errors
+    /// This function will return an error if the file cannot be opened for writing
+    pub fn with_clock(
+        dir: impl AsRef<Path>,
+        prefix: &str,
+        clock: impl Clock + Send + 'static,
+    ) -> io::Result<Self> {
+        Self::open(dir.as_ref(), prefix, Some(Box::new(clock)))
+    }
+    fn open(dir: &Path, prefix: &str, clock: Option<Box<dyn Clock + Send>>) -> io::Result<Self> {
+        let day = unix_day(
+            clock
+                .as_ref()
+                .map_or_else(crate::now_unix_nanos, |c| c.now_unix_nanos()),
+        );
+        let file = open_append(&dir.join(daily_name(prefix, day)))?;
+        Ok(Self {
+            dir: dir.to_owned(),
+            prefix: prefix.to_owned(),
+            keep: None,
+            clock,
+            day,
+            file,
+        })
+    }
+    fn today(&self) -> i64 {
+        unix_day(
+            self.clock
+                .as_ref()
+                .map_or_else(crate::now_unix_nanos, |c| c.now_unix_nanos()),
+        )
+    }
+    /// Keep only the files of the last `days` days (including today)
+    #[inline]
+    #[must_use]
+    pub fn keep(mut self, days: usize) -> Self {
+        self.keep = Some(days);
+        self.prune();
+        self
+    }
+
+    fn roll(&mut self, day: i64) -> io::Result<()> {
+        self.file.flush()?;
+        self.file = open_append(&self.dir.join(daily_name(&self.prefix, day)))?;
+        self.day = day;
+        self.prune();
+        Ok(())
+    }
+
+    /// Best-effort removal of expired files; errors are ignored.
+    fn prune(&self) {
+        let Some(keep) = self.keep else { return };
+        #[allow(clippy::cast_possible_wrap)]
+        let oldest = daily_name(&self.prefix, self.day - keep.max(1) as i64 + 1);
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else { continue };
+            if is_daily_name(&self.prefix, name) && name < oldest.as_str() {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+impl Write for DailyFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let day = self.today();
+        if day != self.day {
+            self.roll(day)?;
+        }
+        self.file.write_all(buf)?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+const fn unix_day(nanos: i128) -> i64 {
+    nanos.div_euclid(86_400 * 1_000_000_000) as i64
+}
+
+fn daily_name(prefix: &str, day: i64) -> String {
+    let (y, m, d) = crate::civil_from_days_utc(day);
+    format!("{prefix}-{y:04}-{m:02}-{d:02}.log")
+}
+
+/// `<prefix>-YYYY-MM-DD.log`
+fn is_daily_name(prefix: &str, name: &str) -> bool {
+    name.strip_prefix(prefix)
+        .and_then(|r| r.strip_prefix('-'))
+        .and_then(|r| r.strip_suffix(".log"))
+        .is_some_and(|date| {
+            date.len() == 10
+                && date.bytes().enumerate().all(|(i, b)| {
+                    if i == 4 || i == 7 {
+                        b == b'-'
+                    } else {
+                        b.is_ascii_digit()
+                    }
+                })
+        })
+}
+
+/// Appending file compressed on the fly with gzip.
+///
+/// Compressed data is buffered, so recent lines are not readable from the file
+/// until [`flush`](Write::flush) (which flushes the encoder) or drop (which
+/// finishes the gzip stream). Appending to an existing file adds a new gzip
+/// member; `gzip -d` and `zcat` read all members back in order.
+#[cfg(feature = "gzip")]
+pub struct GzipFile {
+    enc: Option<flate2::write::GzEncoder<File>>,
+}
+
+#[cfg(feature = "gzip")]
+impl GzipFile {
+    /// Open (or create) `path` for appending compressed output
+    /// # Errors
+    /// This function will return an error if the file cannot be opened for writing.
+    pub fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = open_append(path.as_ref())?;
+        Ok(Self {
+            enc: Some(flate2::write::GzEncoder::new(
+                file,
+                flate2::Compression::default(),
+            )),
+        })
+    }
+    /// Finish the gzip stream and return the underlying file
+    /// # Errors
+    /// Returns any error from writing the gzip trailer
+    pub fn finish(mut self) -> io::Result<File> {
+        self.enc.take().map_or_else(
+            || {
+                Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "gzip stream already finished",
+                ))
+            },
+            flate2::write::GzEncoder::finish,
+        )
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Write for GzipFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.enc
+            .as_mut()
+            .map_or(Ok(buf.len()), |enc| enc.write(buf))
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.enc.as_mut().map_or(Ok(()), Write::flush)
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Drop for GzipFile {
+    fn drop(&mut self) {
+        if let Some(enc) = self.enc.take() {
+            let _ = enc.finish();
+        }
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn ignore_not_found(r: io::Result<()>) -> io::Result<()> {
+    match r {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        r => r,
+    }
+}
+
+/// In-memory writer that keeps the last `capacity` lines, for asserting on your own
+/// logs in tests or showing recent output live.
+///
+/// Clones share one buffer: hand a clone to `set_writer` and read the lines back
+/// through the original.
+#[derive(Clone)]
+pub struct CaptureSink {
+    inner: Arc<Mutex<Capture>>,
+}
+
+struct Capture {
+    lines: VecDeque<String>,
+    capacity: usize,
+    // bytes after the last newline
+    partial: Vec<u8>,
+}
+
+impl CaptureSink {
+    /// Create a sink that keeps the last `capacity` complete lines
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Capture {
+                lines: VecDeque::with_capacity(capacity.min(1024)),
+                capacity,
+                partial: Vec::new(),
+            })),
+        }
+    }
+    /// The captured lines, oldest first, without their newlines
+    /// # Panics
+    /// Panics if the buffer lock is poisoned
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        self.inner.lock().unwrap().lines.iter().cloned().collect()
+    }
+    /// Forget everything captured so far
+    /// # Panics
+    /// Panics if the buffer lock is poisoned
+    pub fn clear(&self) {
+        let mut c = self.inner.lock().unwrap();
+        c.lines.clear();
+        c.partial.clear();
+    }
+}
+
+impl Write for CaptureSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut c = self.inner.lock().unwrap();
+        c.partial.extend_from_slice(buf);
+        while let Some(i) = c.partial.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = c.partial.drain(..=i).collect();
+            if c.capacity == 0 {
+                continue;
+            }
+            if c.lines.len() == c.capacity {
+                c.lines.pop_front();
+            }
+            let line = String::from_utf8_lossy(&line[..i]).into_owned();
+            c.lines.push_back(line);
+        }
+        drop(c);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writer that sends every line as one UDP datagram (for a syslog collector).
+///
+/// Pair it with [`SyslogFormat`](crate::SyslogFormat) so each line is an RFC 5424
+/// message; the trailing newline is not sent. Datagrams that fail to send are
+/// reported through the write but not retried.
+#[cfg(feature = "syslog")]
+pub struct SyslogWriter {
+    socket: std::net::UdpSocket,
+    line: Vec<u8>,
+}
+
+#[cfg(feature = "syslog")]
+impl SyslogWriter {
+    /// Connect a UDP socket (bound to an ephemeral local port) to `addr`
+    /// # Errors
+    /// Returns an error if the socket cannot be bound or connected
+    pub fn new(addr: std::net::SocketAddr) -> io::Result<Self> {
+        let local: std::net::SocketAddr = if addr.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = std::net::UdpSocket::bind(local)?;
+        socket.connect(addr)?;
+        Ok(Self {
+            socket,
+            line: Vec::new(),
+        })
+    }
+    fn send_line(&mut self) -> io::Result<()> {
+        let res = self.socket.send(&self.line).map(drop);
+        self.line.clear();
+        res
+    }
+}
+
+#[cfg(feature = "syslog")]
+impl Write for SyslogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut first_err = None;
+        for chunk in buf.split_inclusive(|&b| b == b'\n') {
+            match chunk.strip_suffix(b"\n") {
+                Some(rest) => {
+                    self.line.extend_from_slice(rest);
+                    if let Err(e) = self.send_line() {
+                        first_err.get_or_insert(e);
+                    }
+                }
+                None => self.line.extend_from_slice(chunk),
+            }
+        }
+        first_err.map_or(Ok(buf.len()), Err)
+    }
+    /// Sends a pending partial line, if any
+    fn flush(&mut self) -> io::Result<()> {
+        if self.line.is_empty() {
+            return Ok(());
+        }
+        self.send_line()
+    }
+}
+
+/// What [`AsyncWriter`] does when its queue is full
+#[cfg(feature = "async-sink")]
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub enum Overflow {
+    /// Wait for the worker to make room (no line is lost)
+    #[default]
+    Block,
+    /// Discard the line being written and count it (see [`AsyncWriter::dropped_count`]);
+    /// the next write that fits is preceded by `WARN  [rustlog] dropped N messages`
+    DropNewest,
+}
+
+#[cfg(feature = "async-sink")]
+enum AsyncMsg {
+    Line(Vec<u8>),
+    Flush(std::sync::mpsc::SyncSender<io::Result<()>>),
+}
+
+/// Writer that hands every write to a background thread, so a slow sink does not
+/// stall the caller.
+///
+/// `flush` waits until everything queued so far reached the inner writer and was
+/// flushed. Dropping the writer (or [`shutdown`](Self::shutdown)) drains the queue
+/// and joins the thread.
+#[cfg(feature = "async-sink")]
+pub struct AsyncWriter {
+    tx: Option<std::sync::mpsc::SyncSender<AsyncMsg>>,
+    worker: Option<std::thread::JoinHandle<()>>,
+    overflow: Overflow,
+    dropped: std::sync::atomic::AtomicU64,
+    // `dropped` value already announced in the stream
+    reported: u64,
+}
+
+#[cfg(feature = "async-sink")]
+impl AsyncWriter {
+    /// Spawn the worker thread; at most `capacity` writes wait in the queue
+    /// # Errors
+    /// Returns an error if the thread cannot be spawned
+    pub fn new(
+        mut inner: Box<dyn Write + Send>,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> io::Result<Self> {
+        let (tx, rx) = std::sync::mpsc::sync_channel::<AsyncMsg>(capacity);
+        let worker = std::thread::Builder::new()
+            .name("rustlog-writer".into())
+            .spawn(move || {
+                for msg in rx {
+                    match msg {
+                        AsyncMsg::Line(bytes) => {
+                            let _ = inner.write_all(&bytes);
+                        }
+                        AsyncMsg::Flush(done) => {
+                            let _ = done.send(inner.flush());
+                        }
+                    }
+                }
+                let _ = inner.flush();
+            })?;
+        Ok(Self {
+            tx: Some(tx),
+            worker: Some(worker),
+            overflow,
+            dropped: std::sync::atomic::AtomicU64::new(0),
+            reported: 0,
+        })
+    }
+    /// Number of writes discarded because the queue was full (`Overflow::DropNewest`)
+    #[inline]
+    #[must_use]
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped.load(std::sync::atomic::Ordering::Relaxed)
+    }
+    /// Drain the queue and join the worker thread
+    #[inline]
+    pub fn shutdown(self) {
+        drop(self);
+    }
+    fn sender(&self) -> io::Result<&std::sync::mpsc::SyncSender<AsyncMsg>> {
+        self.tx.as_ref().ok_or_else(worker_gone)
+    }
+    /// Queue `msg` without blocking; `false` if the queue is full
+    fn try_send(&self, msg: AsyncMsg) -> io::Result<bool> {
+        match self.sender()?.try_send(msg) {
+            Ok(()) => Ok(true),
+            Err(std::sync::mpsc::TrySendError::Full(_)) => Ok(false),
+            Err(std::sync::mpsc::TrySendError::Disconnected(_)) => Err(worker_gone()),
+        }
+    }
+}
+
+#[cfg(feature = "async-sink")]
+fn worker_gone() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "rustlog writer thread is gone")
+}
+
+#[cfg(feature = "async-sink")]
+impl Write for AsyncWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let msg = AsyncMsg::Line(buf.to_vec());
+        match self.overflow {
+            Overflow::Block => self.sender()?.send(msg).map_err(|_| worker_gone())?,
+            Overflow::DropNewest => {
+                let dropped = self.dropped_count();
+                if dropped > self.reported {
+                    let notice = format!(
+                        "WARN  [rustlog] dropped {} messages\n",
+                        dropped - self.reported
+                    );
+                    if self.try_send(AsyncMsg::Line(notice.into_bytes()))? {
+                        self.reported = dropped;
+                    } else {
+                        // still full: the line goes the same way
+                        self.dropped
+                            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        return Ok(buf.len());
+                    }
+                }
+                if !self.try_send(msg)? {
+                    self.dropped
+                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        let (done, wait) = std::sync::mpsc::sync_channel(1);
+        self.sender()?
+            .send(AsyncMsg::Flush(done))
+            .map_err(|_| worker_gone())?;
+        wait.recv().map_err(|_| worker_gone())?
+    }
+}
+
+#[cfg(feature = "async-sink")]
+impl Drop for AsyncWriter {
+    fn drop(&mut self) {
+        drop(self.tx.take());
+        if let Some(w) = self.worker.take() {
+            let _ = w.join();
+        }
+    }
+}
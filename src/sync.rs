@@ -0,0 +1,28 @@
+//! The mutex guarding each logger's sink and writes: `parking_lot`'s with the
+//! `parking_lot` feature (faster under contention), `std`'s otherwise.
+//!
+//! Neither poisons: a writer that panics mid-write fails that one record (the
+//! panic reaches its caller), and later records lock and write as usual.
+
+#[cfg(feature = "parking_lot")]
+pub use parking_lot::Mutex;
+
+/// `std::sync::Mutex` with `parking_lot`'s `lock` signature
+#[cfg(not(feature = "parking_lot"))]
+#[derive(Debug, Default)]
+pub struct Mutex<T: ?Sized>(std::sync::Mutex<T>);
+
+#[cfg(not(feature = "parking_lot"))]
+impl<T> Mutex<T> {
+    pub const fn new(t: T) -> Self {
+        Self(std::sync::Mutex::new(t))
+    }
+}
+
+#[cfg(not(feature = "parking_lot"))]
+impl<T: ?Sized> Mutex<T> {
+    /// Locks, ignoring poisoning: the guarded sink state is valid between writes
+    pub fn lock(&self) -> std::sync::MutexGuard<'_, T> {
+        self.0.lock().unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
@@ -0,0 +1,83 @@
+use core::fmt::{self, Display};
+
+use tracing_core::field::{Field, Visit};
+use tracing_core::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+use crate::Level;
+
+/// A `tracing_subscriber::Layer` that renders events through the global logger.
+///
+/// The event's target becomes the group and its fields are appended as
+/// `key=value`; spans are ignored.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TracingLayer;
+
+/// Returns a [`TracingLayer`] for `registry().with(rustlog::tracing_layer())`
+#[inline]
+#[must_use]
+pub const fn tracing_layer() -> TracingLayer {
+    TracingLayer
+}
+
+const fn level(l: tracing_core::Level) -> Level {
+    match l {
+        tracing_core::Level::TRACE => Level::Trace,
+        tracing_core::Level::DEBUG => Level::Debug,
+        tracing_core::Level::INFO => Level::Info,
+        tracing_core::Level::WARN => Level::Warn,
+        _ => Level::Error,
+    }
+}
+
+/// Collects the `message` field and the remaining fields as strings
+#[derive(Default)]
+struct Fields {
+    message: String,
+    rest: Vec<(&'static str, String)>,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, value);
+    }
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.push(field, format_args!("{value:?}"));
+    }
+}
+
+impl Fields {
+    fn push(&mut self, field: &Field, value: impl Display) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.rest.push((field.name(), value.to_string()));
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for TracingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let l = level(*meta.level());
+        if !crate::enabled(l, Some(meta.target())) {
+            return;
+        }
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let kv: Vec<(&str, &dyn Display)> = fields
+            .rest
+            .iter()
+            .map(|(k, v)| (*k, v as &dyn Display))
+            .collect();
+        crate::emit_kv(
+            l,
+            Some(meta.target()),
+            meta.module_path().unwrap_or(""),
+            meta.file().unwrap_or("<unknown>"),
+            meta.line().unwrap_or(0),
+            format_args!("{}", fields.message),
+            &kv,
+        );
+    }
+}
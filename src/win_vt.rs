@@ -0,0 +1,40 @@
+//! Enables ANSI escape processing on Windows 10+ consoles.
+#![allow(unsafe_code)]
+
+use std::sync::OnceLock;
+
+use windows_sys::Win32::Foundation::INVALID_HANDLE_VALUE;
+use windows_sys::Win32::System::Console::{
+    GetConsoleMode, GetStdHandle, SetConsoleMode, CONSOLE_MODE, ENABLE_VIRTUAL_TERMINAL_PROCESSING,
+    STD_ERROR_HANDLE, STD_HANDLE, STD_OUTPUT_HANDLE,
+};
+
+/// Returns `true` if VT processing is on for stdout; the console is switched once
+pub fn stdout() -> bool {
+    static ON: OnceLock<bool> = OnceLock::new();
+    *ON.get_or_init(|| enable(STD_OUTPUT_HANDLE))
+}
+
+/// Returns `true` if VT processing is on for stderr; the console is switched once
+pub fn stderr() -> bool {
+    static ON: OnceLock<bool> = OnceLock::new();
+    *ON.get_or_init(|| enable(STD_ERROR_HANDLE))
+}
+
+fn enable(which: STD_HANDLE) -> bool {
+    // SAFETY: plain Win32 calls on the process' own std handle; `mode` outlives the call.
+    unsafe {
+        let h = GetStdHandle(which);
+        if h.is_null() || h == INVALID_HANDLE_VALUE {
+            return false;
+        }
+        let mut mode: CONSOLE_MODE = 0;
+        if GetConsoleMode(h, &mut mode) == 0 {
+            return false;
+        }
+        if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+            return true;
+        }
+        SetConsoleMode(h, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+    }
+}
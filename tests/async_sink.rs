@@ -0,0 +1,93 @@
+#![cfg(feature = "async-sink")]
+use std::io::{self, Write};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rustlog::info;
+use rustlog::sink::{AsyncWriter, Overflow};
+
+#[derive(Clone, Default)]
+struct Slow(Arc<Mutex<Vec<u8>>>);
+impl Write for Slow {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        std::thread::sleep(Duration::from_millis(1));
+        self.0.lock().unwrap().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn async_writer_delivers_everything_by_flush() {
+    let slow = Slow::default();
+    rustlog::set_async_writer(Box::new(slow.clone()), 4).unwrap();
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+    rustlog::set_level(rustlog::Level::Info);
+
+    for i in 0..50 {
+        info!("line {i}");
+    }
+    rustlog::flush().unwrap();
+
+    let text = String::from_utf8(slow.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 50);
+    assert_eq!(lines[49], "INFO  line 49");
+    rustlog::reset_target();
+}
+
+/// Signals when the worker picked up a write, then blocks until released
+struct Gate {
+    started: mpsc::Sender<()>,
+    release: Arc<Mutex<()>>,
+    out: Arc<Mutex<Vec<u8>>>,
+}
+impl Write for Gate {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        let _ = self.started.send(());
+        let _g = self.release.lock().unwrap();
+        self.out.lock().unwrap().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn drop_newest_counts_rejected_writes() {
+    let (started, wait_started) = mpsc::channel();
+    let release = Arc::new(Mutex::new(()));
+    let out = Arc::new(Mutex::new(Vec::new()));
+    let held = release.lock().unwrap();
+    let gate = Gate {
+        started,
+        release: release.clone(),
+        out: out.clone(),
+    };
+    let mut w = AsyncWriter::new(Box::new(gate), 2, Overflow::DropNewest).unwrap();
+
+    w.write_all(b"0\n").unwrap();
+    wait_started.recv().unwrap(); // worker is now stuck on line 0
+    for i in 1..10 {
+        w.write_all(format!("{i}\n").as_bytes()).unwrap();
+    }
+    assert_eq!(w.dropped_count(), 7, "two fit in the queue");
+
+    drop(held);
+    w.flush().unwrap();
+    assert_eq!(&*out.lock().unwrap(), b"0\n1\n2\n");
+
+    // the next write that fits announces the loss first
+    w.write_all(b"10\n").unwrap();
+    w.flush().unwrap();
+    let text = String::from_utf8(out.lock().unwrap().clone()).unwrap();
+    assert_eq!(text, "0\n1\n2\nWARN  [rustlog] dropped 7 messages\n10\n");
+    assert_eq!(w.dropped_count(), 7);
+    w.shutdown();
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+use rustlog::{info, ColorMode};
+
+#[test]
+fn auto_stays_plain_for_writer_and_mode_changes_apply() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    std::env::remove_var("NO_COLOR");
+    std::env::remove_var("CLICOLOR_FORCE");
+
+    rustlog::set_color_mode(ColorMode::Auto);
+    info!("auto 1");
+    info!("auto 2");
+    rustlog::set_color_mode(ColorMode::Always);
+    info!("always");
+    rustlog::set_color_mode(ColorMode::Auto);
+    info!("auto 3");
+    rustlog::set_color_mode(ColorMode::Never);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines[0], "INFO  auto 1");
+    assert_eq!(lines[1], "INFO  auto 2");
+    assert!(lines[2].starts_with("\x1b["), "{:?}", lines[2]);
+    assert_eq!(lines[3], "INFO  auto 3");
+}
+
+#[test]
+fn auto_on_std_streams_is_stable() {
+    let _g = test_lock().lock().unwrap();
+    let lg = rustlog::local::Logger::builder()
+        .stdout()
+        .color_mode(ColorMode::Auto)
+        .build()
+        .unwrap();
+    // the first answer is cached, so repeated emits keep working
+    for i in 0..3 {
+        rustlog::local::info!(&lg, "stdout auto {i}");
+    }
+}
@@ -11,3 +11,15 @@ fn banner_has_trailing_newline() {
     let bytes = buf.lock().unwrap().clone();
     assert!(bytes.ends_with(b"\n"), "banner must end with a newline");
 }
+
+#[test]
+fn banner_fn_prints_crate_meta() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    rustlog::banner();
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines, [format!("rustlog v{}", env!("CARGO_PKG_VERSION"))]);
+}
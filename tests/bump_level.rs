@@ -0,0 +1,43 @@
+mod helpers;
+use helpers::*;
+use rustlog::Level;
+
+#[test]
+fn bump_from_info_saturates_at_trace() {
+    let _g = test_lock().lock().unwrap();
+    rustlog::set_level(Level::Info);
+
+    assert_eq!(rustlog::bump_level(), Level::Debug);
+    assert_eq!(rustlog::bump_level(), Level::Trace);
+    for _ in 0..3 {
+        assert_eq!(rustlog::bump_level(), Level::Trace);
+    }
+    assert_eq!(rustlog::level(), Level::Trace);
+}
+
+#[test]
+fn reduce_saturates_at_fatal() {
+    let _g = test_lock().lock().unwrap();
+    rustlog::set_level(Level::Error);
+
+    assert_eq!(rustlog::reduce_level(), Level::Fatal);
+    assert_eq!(rustlog::reduce_level(), Level::Fatal);
+    assert_eq!(rustlog::bump_level(), Level::Error);
+    rustlog::set_level(Level::Trace);
+}
+
+#[test]
+fn concurrent_bumps_each_take_a_step() {
+    let _g = test_lock().lock().unwrap();
+    rustlog::set_level(Level::Fatal);
+
+    let handles: Vec<_> = (0..Level::Fatal as u8)
+        .map(|_| std::thread::spawn(rustlog::bump_level))
+        .collect();
+    for h in handles {
+        h.join().unwrap();
+    }
+
+    assert_eq!(rustlog::level(), Level::Trace);
+    rustlog::set_level(Level::Trace);
+}
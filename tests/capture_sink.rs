@@ -0,0 +1,23 @@
+use rustlog::sink::CaptureSink;
+use rustlog::{info, warn, Level, Target};
+
+#[test]
+fn captures_the_last_lines() {
+    let cap = CaptureSink::new(2);
+    rustlog::set_writer(Box::new(cap.clone()));
+    rustlog::set_target(Target::Writer);
+    rustlog::set_level(Level::Info);
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+
+    info!("one");
+    info!("two");
+    warn!("three");
+    assert_eq!(cap.lines(), ["INFO  two", "WARN  three"]);
+
+    cap.clear();
+    assert!(cap.lines().is_empty());
+    info!("multi\nline");
+    assert_eq!(cap.lines(), ["INFO  multi", "line"]);
+}
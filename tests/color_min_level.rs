@@ -0,0 +1,28 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+use rustlog::{error, info, warn, ColorMode, Level};
+
+#[test]
+fn only_lines_at_the_threshold_are_colored() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_color_mode(ColorMode::Always);
+    assert_eq!(rustlog::color_min_level(), Level::Trace);
+
+    rustlog::set_color_min_level(Level::Warn);
+    info!("plain");
+    warn!("warned");
+    error!("failed");
+    rustlog::set_color_min_level(Level::Trace);
+    info!("colored again");
+    rustlog::set_color_mode(ColorMode::Never);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines[0], "INFO  plain");
+    assert!(lines[1].contains("\x1b[33m"), "{:?}", lines[1]);
+    assert!(lines[2].contains("\x1b[31m"), "{:?}", lines[2]);
+    assert!(lines[3].contains("\x1b[32m"), "{:?}", lines[3]);
+}
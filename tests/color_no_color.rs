@@ -0,0 +1,80 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+use rustlog::{info, ColorMode};
+
+fn has_ansi(s: &str) -> bool {
+    s.contains("\u{1b}[")
+}
+
+fn clear_env() {
+    std::env::remove_var("NO_COLOR");
+    std::env::remove_var("CLICOLOR_FORCE");
+    std::env::remove_var("RUST_LOG_COLOR");
+}
+
+fn log_once() -> String {
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    info!("hello");
+    let bytes = buf.lock().unwrap().clone();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn clicolor_force_enables_ansi_in_auto_mode() {
+    let _g = test_lock().lock().unwrap();
+    clear_env();
+    std::env::set_var("CLICOLOR_FORCE", "1");
+    rustlog::set_color_mode(ColorMode::Auto);
+    rustlog::init_from_env();
+
+    let text = log_once();
+    assert!(has_ansi(&text), "CLICOLOR_FORCE should force ANSI: {text}");
+
+    std::env::set_var("CLICOLOR_FORCE", "0");
+    rustlog::init_from_env();
+    let text = log_once();
+    assert!(!has_ansi(&text), "CLICOLOR_FORCE=0 should not force ANSI: {text}");
+
+    clear_env();
+    rustlog::init_from_env();
+}
+
+#[test]
+fn no_color_wins_over_clicolor_force() {
+    let _g = test_lock().lock().unwrap();
+    clear_env();
+    std::env::set_var("NO_COLOR", "1");
+    std::env::set_var("CLICOLOR_FORCE", "1");
+    rustlog::set_color_mode(ColorMode::Auto);
+    rustlog::init_from_env();
+
+    let text = log_once();
+    assert!(!has_ansi(&text), "NO_COLOR should disable ANSI: {text}");
+
+    // explicit mode still overrides the environment
+    rustlog::set_color_mode(ColorMode::Always);
+    let text = log_once();
+    assert!(has_ansi(&text), "ColorMode::Always should override NO_COLOR: {text}");
+
+    rustlog::set_color_mode(ColorMode::Auto);
+    clear_env();
+    rustlog::init_from_env();
+}
+
+#[test]
+fn empty_no_color_is_ignored() {
+    let _g = test_lock().lock().unwrap();
+    clear_env();
+    std::env::set_var("NO_COLOR", "");
+    std::env::set_var("CLICOLOR_FORCE", "1");
+    rustlog::set_color_mode(ColorMode::Auto);
+    rustlog::init_from_env();
+
+    let text = log_once();
+    assert!(has_ansi(&text), "empty NO_COLOR must be ignored: {text}");
+
+    clear_env();
+    rustlog::init_from_env();
+}
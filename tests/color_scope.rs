@@ -0,0 +1,69 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+use rustlog::{info_group, warn, ColorMode, ColorScope};
+
+#[test]
+fn whole_line_starts_with_the_level_color() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_show_time(true);
+    rustlog::set_color_mode(ColorMode::Always);
+    rustlog::set_color_scope(ColorScope::WholeLine);
+
+    warn!("careful");
+    info_group!("net", "up");
+
+    rustlog::set_color_scope(ColorScope::LevelOnly);
+    rustlog::set_color_mode(ColorMode::Never);
+    let lines = lines_from(&buf);
+
+    assert!(lines[0].starts_with("\x1b[33m"), "{:?}", lines[0]);
+    assert!(lines[0].ends_with("WARN  careful\x1b[0m"), "{:?}", lines[0]);
+    // one reset per line: the group tag doesn't close the color early
+    assert_eq!(lines[1].matches("\x1b[0m").count(), 1, "{:?}", lines[1]);
+    assert!(lines[1].ends_with("INFO  [net] up\x1b[0m"), "{:?}", lines[1]);
+}
+
+#[test]
+fn level_only_is_the_default() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_color_mode(ColorMode::Always);
+
+    warn!("careful");
+
+    rustlog::set_color_mode(ColorMode::Never);
+    assert_eq!(rustlog::color_scope(), ColorScope::LevelOnly);
+    assert_eq!(lines_from(&buf)[0], "\x1b[33mWARN \x1b[0m careful");
+}
+
+#[test]
+fn scope_is_per_logger() {
+    use rustlog::local::{warn as lwarn, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_color_mode(ColorMode::Always)
+        .set_color_scope(ColorScope::WholeLine)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_color_scope(ColorScope::LevelOnly);
+
+    lwarn!(&lg, "parent");
+    lwarn!(&child, "child");
+
+    let lines = cap.lines();
+    assert_eq!(lines[0], "\x1b[33mWARN  parent\x1b[0m");
+    assert_eq!(lines[1], "\x1b[33mWARN \x1b[0m child");
+}
@@ -28,3 +28,59 @@ fn lines_are_atomic_under_concurrency() {
         assert!(l.contains("INFO"));
     }
 }
+
+#[derive(Clone, Default)]
+struct Mem(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+impl std::io::Write for Mem {
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        // hand over a byte at a time so a torn write would show up as interleaving
+        let mut v = self.0.lock().unwrap();
+        v.push(bytes[0]);
+        drop(v);
+        std::thread::yield_now();
+        Ok(1)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn separate_loggers_keep_lines_whole() {
+    use rustlog::local::{info as linfo, Logger};
+
+    let (a, b) = (Mem::default(), Mem::default());
+    let build = |m: &Mem| {
+        Logger::builder()
+            .set_writer(Box::new(m.clone()))
+            .set_show_time(false)
+            .set_show_thread_id(false)
+            .set_show_file_line(false)
+            .build()
+            .unwrap()
+    };
+    let (la, lb) = (build(&a), build(&b));
+
+    std::thread::scope(|s| {
+        for t in 0..N_THREADS {
+            let (la, lb) = (&la, &lb);
+            s.spawn(move || {
+                for i in 0..N_LINES {
+                    linfo!(la, "a T{t} #{i}");
+                    linfo!(lb, "b T{t} #{i}");
+                }
+            });
+        }
+    });
+
+    for (name, m) in [("a", &a), ("b", &b)] {
+        let text = String::from_utf8(m.0.lock().unwrap().clone()).unwrap();
+        let mut lines: Vec<&str> = text.lines().collect();
+        lines.sort_unstable();
+        let mut want: Vec<String> = (0..N_THREADS)
+            .flat_map(|t| (0..N_LINES).map(move |i| format!("INFO  {name} T{t} #{i}")))
+            .collect();
+        want.sort_unstable();
+        assert_eq!(lines, want);
+    }
+}
@@ -0,0 +1,36 @@
+mod helpers;
+use helpers::*;
+
+use rustlog::{info, push_context};
+
+#[test]
+fn context_applies_until_guard_drops() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    {
+        let _ctx = push_context("request_id", "abc".to_string());
+        let _user = push_context("user", "ann lee");
+        info!("handling");
+    }
+    info!("idle");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines[0], r#"INFO  request_id=abc user="ann lee" handling"#);
+    assert_eq!(lines[1], "INFO  idle");
+}
+
+#[test]
+fn context_is_per_thread() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    let _ctx = push_context("request_id", "main");
+    std::thread::spawn(|| info!("other thread")).join().unwrap();
+
+    assert_eq!(lines_from(&buf), ["INFO  other thread"]);
+}
@@ -0,0 +1,37 @@
+mod helpers;
+use helpers::*;
+
+use std::io::{self, Write};
+
+use rustlog::local::{info as linfo, Logger};
+use rustlog::{DefaultFormat, Format, Record};
+
+struct Arrow;
+impl Format for Arrow {
+    fn write(&self, w: &mut dyn Write, r: &Record) -> io::Result<()> {
+        writeln!(w, "{} {} -> {}", r.level, r.group.unwrap_or("-"), r.args)
+    }
+}
+
+#[test]
+fn custom_format_replaces_layout() -> io::Result<()> {
+    let mem = Mem::default();
+    rustlog::set_writer(Box::new(mem.clone()));
+    rustlog::set_format(Box::new(Arrow));
+    rustlog::info_group!("net", "up {}", 1);
+    rustlog::set_format(Box::new(DefaultFormat));
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+    rustlog::warn!("back");
+    assert_eq!(mem.text(), "info net -> up 1\nWARN  back\n");
+
+    let local = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(local.clone()))
+        .set_format(Box::new(Arrow))
+        .build()?;
+    linfo!(&lg, "local");
+    assert_eq!(local.text(), "info - -> local\n");
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rustlog::local::{info as linfo, Logger};
+use rustlog::sink::DailyFile;
+use rustlog::Clock;
+
+fn temp_dir(tag: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!(
+        "rustlog_daily_{tag}_{}_{}",
+        std::process::id(),
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    fs::create_dir_all(&p).unwrap();
+    p
+}
+
+// 2024-01-15 00:00:00 UTC
+const JAN_15: u64 = 1_705_276_800;
+
+struct FakeClock(Arc<AtomicU64>);
+impl Clock for FakeClock {
+    fn now_unix_nanos(&self) -> i128 {
+        i128::from(self.0.load(Ordering::Relaxed)) * 1_000_000_000
+    }
+}
+
+fn fake_clock(secs: &Arc<AtomicU64>) -> FakeClock {
+    FakeClock(Arc::clone(secs))
+}
+
+#[test]
+fn rolls_at_utc_midnight() -> std::io::Result<()> {
+    let dir = temp_dir("roll");
+    let secs = Arc::new(AtomicU64::new(JAN_15 + 86_399));
+    let sink = DailyFile::with_clock(&dir, "app", fake_clock(&secs))?;
+    let lg = Logger::builder()
+        .set_writer(Box::new(sink))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+
+    linfo!(&lg, "before midnight");
+    secs.store(JAN_15 + 86_400, Ordering::Relaxed);
+    linfo!(&lg, "after midnight");
+
+    let day1 = fs::read_to_string(dir.join("app-2024-01-15.log"))?;
+    let day2 = fs::read_to_string(dir.join("app-2024-01-16.log"))?;
+    assert_eq!(day1, "INFO  before midnight\n");
+    assert_eq!(day2, "INFO  after midnight\n");
+    assert_eq!(fs::read_dir(&dir)?.count(), 2);
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn keep_deletes_old_days() -> std::io::Result<()> {
+    let dir = temp_dir("keep");
+    fs::write(dir.join("app-2024-01-10.log"), "old\n")?;
+    fs::write(dir.join("other.log"), "untouched\n")?;
+    let secs = Arc::new(AtomicU64::new(JAN_15));
+    let mut sink = DailyFile::with_clock(&dir, "app", fake_clock(&secs))?.keep(2);
+    assert!(!dir.join("app-2024-01-10.log").exists());
+
+    for day in 0..3 {
+        secs.store(JAN_15 + day * 86_400, Ordering::Relaxed);
+        sink.write_all(b"line\n")?;
+    }
+
+    assert!(!dir.join("app-2024-01-15.log").exists());
+    assert!(dir.join("app-2024-01-16.log").exists());
+    assert!(dir.join("app-2024-01-17.log").exists());
+    assert!(dir.join("other.log").exists());
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn builder_opens_todays_file() -> std::io::Result<()> {
+    let dir = temp_dir("builder");
+    let lg = Logger::builder().daily_file(&dir, "svc").build()?;
+    linfo!(&lg, "hello");
+    let names: Vec<_> = fs::read_dir(&dir)?
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert_eq!(names.len(), 1);
+    assert!(names[0].starts_with("svc-"));
+    assert_eq!(std::path::Path::new(&names[0]).extension().unwrap(), "log");
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+mod helpers;
+use helpers::*;
+use rustlog::info;
+use std::time::Duration;
+
+#[test]
+fn second_line_shows_time_since_first() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    rustlog::set_show_delta(true);
+    info!("first");
+    std::thread::sleep(Duration::from_millis(20));
+    info!("second");
+    rustlog::set_show_delta(false);
+    info!("third");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "INFO  first");
+    assert!(lines[2].starts_with("INFO  third"), "{:?}", lines[2]);
+
+    // `(+20.123 ms) INFO  second`
+    let rest = lines[1].strip_prefix("(+").expect("delta token");
+    let (value, rest) = rest.split_once(' ').unwrap();
+    assert!(rest.starts_with("ms) INFO  second"), "{:?}", lines[1]);
+    let ms: f64 = value.parse().unwrap();
+    assert!((20.0..10_000.0).contains(&ms), "{:?}", lines[1]);
+}
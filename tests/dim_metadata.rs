@@ -0,0 +1,62 @@
+#![cfg(all(feature = "color", feature = "timestamp"))]
+mod helpers;
+use helpers::*;
+use rustlog::{info, ColorMode};
+
+#[test]
+fn faint_wraps_metadata_but_not_the_message() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_time(true);
+    rustlog::set_show_file_line(true);
+    rustlog::set_color_mode(ColorMode::Always);
+    rustlog::set_dim_metadata(true);
+    info!("hello");
+    rustlog::set_dim_metadata(false);
+    info!("bright");
+    rustlog::set_color_mode(ColorMode::Never);
+    rustlog::set_show_time(false);
+
+    let lines = lines_from(&buf);
+    // `\x1b[2m2025-01-01 12:00:00.000\x1b[0m INFO ...`
+    let ts = lines[0].strip_prefix("\x1b[2m").expect("faint timestamp");
+    let (stamp, rest) = ts.split_once("\x1b[0m").unwrap();
+    assert!(
+        stamp.starts_with(|c: char| c.is_ascii_digit()),
+        "{:?}",
+        lines[0]
+    );
+    assert!(!stamp.contains("INFO"), "{:?}", lines[0]);
+    assert!(rest.contains("\x1b[2m<"), "{:?}", lines[0]);
+    let msg_at = rest.rfind("hello").unwrap();
+    assert!(rest[..msg_at].ends_with("\x1b[0m "), "{:?}", lines[0]);
+    assert!(!rest[msg_at..].contains('\x1b'), "{:?}", lines[0]);
+    assert!(!lines[1].contains("\x1b[2m"), "{:?}", lines[1]);
+}
+
+#[test]
+fn dim_is_per_logger() {
+    use rustlog::local::{info as linfo, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(true)
+        .set_color_mode(ColorMode::Always)
+        .set_dim_metadata(true)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_dim_metadata(false);
+
+    linfo!(&lg, "parent");
+    linfo!(&child, "child");
+
+    let lines = cap.lines();
+    assert!(lines[0].contains("\x1b[2m<"), "{:?}", lines[0]);
+    assert!(!lines[1].contains("\x1b[2m"), "{:?}", lines[1]);
+}
@@ -0,0 +1,80 @@
+mod helpers;
+use helpers::*;
+
+use std::io::{self, Write};
+
+use rustlog::local::{info as linfo, Logger};
+
+const N_THREADS: usize = 4;
+const N_LINES: usize = 20;
+
+/// Takes at most 7 bytes per call, so a line split across calls would interleave
+#[derive(Clone, Default)]
+struct Choppy(Mem);
+impl Write for Choppy {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        let n = self.0.write(&bytes[..bytes.len().min(7)])?;
+        std::thread::yield_now();
+        Ok(n)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn logger(m: &Choppy) -> Logger {
+    Logger::builder()
+        .set_writer(Box::new(m.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_direct_write(true)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn direct_lines_are_atomic_under_concurrency() {
+    let m = Choppy::default();
+    let lg = logger(&m);
+    assert!(lg.direct_write());
+    // the sink takes it in many pieces, which must not interleave
+    let big = "x".repeat(4096);
+
+    std::thread::scope(|s| {
+        for t in 0..N_THREADS {
+            let (lg, big) = (&lg, &big);
+            s.spawn(move || {
+                for i in 0..N_LINES {
+                    linfo!(lg, "T{t} #{i} {big}");
+                }
+            });
+        }
+    });
+
+    let text = m.0.text();
+    let mut lines: Vec<&str> = text.lines().collect();
+    lines.sort_unstable();
+    let mut want: Vec<String> = (0..N_THREADS)
+        .flat_map(|t| (0..N_LINES).map(move |i| format!("INFO  T{t} #{i} {}", "x".repeat(4096))))
+        .collect();
+    want.sort_unstable();
+    assert_eq!(lines, want);
+}
+
+#[test]
+fn direct_matches_buffered_output() {
+    let (direct, buffered) = (Choppy::default(), Choppy::default());
+    let lg = logger(&direct);
+    let plain = logger(&buffered);
+    plain.set_direct_write(false);
+
+    for lg in [&lg, &plain] {
+        rustlog::local::info_group!(lg, "db", "multi\nline");
+        rustlog::local::warn!(lg, "done");
+    }
+
+    let direct = direct.0.text();
+    assert_eq!(direct, buffered.0.text());
+    assert_eq!(direct, "INFO  [db] multi\nline\nWARN  done\n");
+}
@@ -0,0 +1,74 @@
+mod helpers;
+use helpers::*;
+
+use rustlog::local::Logger;
+use rustlog::sink::CaptureSink;
+use rustlog::{info, Level};
+
+#[test]
+fn literal_and_formatted_messages_render_alike() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    info!("ready");
+    info!("{}", "ready");
+    rustlog::set_max_message_len(Some(3));
+    info!("ready");
+    info!("{}", "ready");
+    rustlog::set_max_message_len(None);
+    rustlog::set_sanitize(true);
+    info!("a\tb");
+    info!("{}", "a\tb");
+    rustlog::set_sanitize(false);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines[0], "INFO  ready");
+    for pair in lines.chunks(2) {
+        assert_eq!(pair[0], pair[1]);
+    }
+    assert_eq!(lines[2], "INFO  rea…(truncated)");
+    assert_eq!(lines[4], "INFO  a\\tb");
+}
+
+#[test]
+fn emit_str_matches_the_macros() {
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()
+        .unwrap();
+
+    let runtime = String::from("{not a placeholder}");
+    lg.emit_str(Level::Info, Some("db"), file!(), line!(), &runtime);
+    rustlog::local::info_group!(&lg, "db", "{{not a placeholder}}");
+
+    let lines = cap.lines();
+    assert_eq!(lines[0], "INFO  [db] {not a placeholder}");
+    assert_eq!(lines[0], lines[1]);
+}
+
+#[test]
+fn emit_str_copies_the_text_without_formatting() {
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_sanitize(true)
+        .set_max_message_len(12)
+        // only records carrying the raw text get through
+        .set_filter(Box::new(|r| r.msg.is_some()))
+        .build()
+        .unwrap();
+
+    lg.emit_str(Level::Warn, None, file!(), line!(), "{}\t{x} {{and more}}");
+    rustlog::local::warn!(&lg, "formatted {}", 1);
+
+    assert_eq!(cap.lines(), ["WARN  {}\\t{x} {{and…(truncated)"]);
+}
@@ -0,0 +1,50 @@
+mod helpers;
+use helpers::*;
+
+use rustlog::local::{error as lerror, info as linfo, Logger};
+use rustlog::{debug, error, info, info_group, warn, Level};
+
+#[test]
+fn global_counts_written_lines_per_level() {
+    let _g = test_lock().lock().unwrap();
+    let (_buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_level(Level::Info);
+    rustlog::reset_counts();
+    rustlog::set_filter(Box::new(|r| r.group != Some("muted")));
+
+    info!("a");
+    info!("b");
+    info_group!("muted", "filtered");
+    debug!("below the level");
+    warn!("c");
+    error!("d");
+
+    rustlog::clear_filter();
+    let counts = Level::all().map(rustlog::emitted_count);
+    assert_eq!(counts, [0, 0, 2, 0, 1, 1, 0]);
+
+    rustlog::reset_counts();
+    assert!(Level::all().into_iter().all(|l| rustlog::emitted_count(l) == 0));
+}
+
+#[test]
+fn logger_counts_are_its_own() {
+    let cap = rustlog::sink::CaptureSink::new(8);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .build()
+        .unwrap();
+    linfo!(&lg, "one");
+    lerror!(&lg, "two");
+    lerror!(&lg, "three");
+
+    let child = lg.child();
+    linfo!(&child, "child");
+
+    assert_eq!(lg.emitted_count(Level::Info), 1);
+    assert_eq!(lg.emitted_count(Level::Error), 2);
+    assert_eq!(child.emitted_count(Level::Info), 1);
+    assert_eq!(child.emitted_count(Level::Error), 0);
+    assert_eq!(cap.lines().len(), 4);
+}
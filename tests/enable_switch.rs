@@ -0,0 +1,49 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::local::Logger;
+use rustlog::{error, fatal, info, info_group, scope_time};
+
+#[test]
+fn global_switch_silences_and_resumes() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    assert!(rustlog::is_enabled());
+    rustlog::set_enabled(false);
+    assert!(!rustlog::is_enabled());
+    info!("hidden");
+    fatal!("hidden too");
+    info_group!("net", "hidden group");
+    scope_time!("hidden timer", {});
+    assert!(lines_from(&buf).is_empty(), "{:?}", lines_from(&buf));
+
+    rustlog::set_enabled(true);
+    info!("visible");
+    error!("visible too");
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 2, "{lines:?}");
+    assert!(lines[0].ends_with("visible"));
+}
+
+#[test]
+fn local_switch_silences_and_resumes() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .build()?;
+
+    lg.set_enabled(false);
+    assert!(!lg.is_enabled());
+    rustlog::local::fatal!(&lg, "hidden");
+    assert!(mem.text().is_empty());
+
+    lg.set_enabled(true);
+    rustlog::local::info!(&lg, "visible");
+    let s = mem.text();
+    assert!(s.trim_end().ends_with("visible"), "{s}");
+    Ok(())
+}
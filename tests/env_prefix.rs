@@ -0,0 +1,33 @@
+mod helpers;
+use helpers::*;
+use rustlog::{EnvError, Level};
+
+#[test]
+fn prefixed_level_is_read() {
+    let _g = test_lock().lock().unwrap();
+    reset_runtime();
+    rustlog::set_level(Level::Info);
+
+    std::env::remove_var("RUST_LOG_LEVEL");
+    std::env::set_var("MYAPP_LEVEL", "warn");
+    rustlog::init_from_env_prefixed("MYAPP");
+    std::env::remove_var("MYAPP_LEVEL");
+
+    assert_eq!(rustlog::level(), Level::Warn);
+    assert!(std::env::var_os("RUST_LOG_LEVEL").is_none());
+}
+
+#[test]
+fn prefixed_errors_name_the_prefixed_var() {
+    let _g = test_lock().lock().unwrap();
+    reset_runtime();
+
+    std::env::set_var("MYAPP_LEVEL", "loud");
+    let err = rustlog::try_init_from_env_prefixed("MYAPP").unwrap_err();
+    std::env::remove_var("MYAPP_LEVEL");
+
+    assert!(
+        matches!(&err, EnvError::Level { var, .. } if var == "MYAPP_LEVEL"),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,55 @@
+mod helpers;
+use helpers::*;
+use rustlog::{ColorMode, EnvError, Level};
+
+#[test]
+fn bad_level_is_reported_and_nothing_changes() {
+    let _g = test_lock().lock().unwrap();
+    reset_runtime();
+    rustlog::set_level(Level::Info);
+
+    std::env::set_var("RUST_LOG_LEVEL", "loud");
+    std::env::set_var("RUST_LOG_COLOR", "never");
+    let err = rustlog::try_init_from_env().unwrap_err();
+    std::env::remove_var("RUST_LOG_LEVEL");
+    std::env::remove_var("RUST_LOG_COLOR");
+
+    assert!(
+        matches!(&err, EnvError::Level { var, value } if var == "RUST_LOG_LEVEL" && value == "loud"),
+        "{err:?}"
+    );
+    assert!(err.to_string().contains("RUST_LOG_LEVEL=\"loud\""), "{err}");
+    assert_eq!(rustlog::level(), Level::Info);
+}
+
+#[test]
+fn bad_color_is_reported() {
+    let _g = test_lock().lock().unwrap();
+    reset_runtime();
+
+    std::env::set_var("RUST_LOG_COLOR", "rainbow");
+    let err = rustlog::try_init_from_env().unwrap_err();
+    // the lenient variant falls back to `auto`
+    rustlog::set_color_mode(ColorMode::Never);
+    rustlog::init_from_env();
+    std::env::remove_var("RUST_LOG_COLOR");
+
+    assert!(
+        matches!(&err, EnvError::Color { var, value } if var == "RUST_LOG_COLOR" && value == "rainbow"),
+        "{err:?}"
+    );
+    assert_eq!(rustlog::color_mode(), ColorMode::Auto);
+}
+
+#[test]
+fn valid_values_pass() {
+    let _g = test_lock().lock().unwrap();
+    reset_runtime();
+
+    std::env::set_var("RUST_LOG_LEVEL", "WARN");
+    let res = rustlog::try_init_from_env();
+    std::env::remove_var("RUST_LOG_LEVEL");
+
+    res.unwrap();
+    assert_eq!(rustlog::level(), Level::Warn);
+}
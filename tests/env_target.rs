@@ -0,0 +1,111 @@
+use std::fs;
+use std::process::Command;
+use std::sync::Mutex;
+
+use rustlog::EnvError;
+
+const CHILD_ENV: &str = "RUSTLOG_ENV_TARGET_CHILD";
+// children inherit our env, so don't spawn them while a test is changing it
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+fn temp_path(tag: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!(
+        "rustlog_env_{tag}_{}_{}.log",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ))
+}
+
+// Runs only inside the subprocesses spawned below; the parent sets the env.
+#[test]
+fn env_child() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    rustlog::init_from_env();
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+    rustlog::set_color_mode(rustlog::ColorMode::Never);
+    rustlog::info!("from env");
+    rustlog::flush().unwrap();
+}
+
+fn run_child(vars: &[(&str, &str)]) -> (String, String) {
+    let _g = ENV_LOCK.lock().unwrap();
+    let out = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "env_child", "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, "1")
+        .envs(vars.iter().copied())
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    (
+        String::from_utf8(out.stdout).unwrap(),
+        String::from_utf8(out.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn target_stdout_from_env() {
+    let (stdout, stderr) = run_child(&[("RUST_LOG_TARGET", "stdout")]);
+    assert!(stdout.contains("INFO  from env\n"), "{stdout}");
+    assert!(!stderr.contains("from env"), "{stderr}");
+}
+
+#[test]
+fn file_and_format_from_env() {
+    let path = temp_path("file");
+    let (stdout, stderr) = run_child(&[
+        ("RUST_LOG_FILE", path.to_str().unwrap()),
+        ("RUST_LOG_TARGET", "stdout"), // the file wins
+        ("RUST_LOG_FORMAT", "logfmt"),
+    ]);
+    let written = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(written, "level=info msg=\"from env\"\n");
+    assert!(!stdout.contains("from env") && !stderr.contains("from env"));
+}
+
+#[test]
+fn unopenable_file_falls_back_to_stderr() {
+    let dir = std::env::temp_dir(); // a directory can't be opened for writing
+    let (stdout, stderr) = run_child(&[("RUST_LOG_FILE", dir.to_str().unwrap())]);
+    assert!(stderr.contains("INFO  from env\n"), "{stderr}");
+    assert!(!stdout.contains("from env"), "{stdout}");
+}
+
+#[test]
+fn strict_init_reports_file_and_values() {
+    if std::env::var_os(CHILD_ENV).is_some() {
+        return;
+    }
+    let _g = ENV_LOCK.lock().unwrap();
+    let dir = std::env::temp_dir();
+    std::env::set_var("RUST_LOG_FILE", &dir);
+    let file_err = rustlog::try_init_from_env().unwrap_err();
+    std::env::remove_var("RUST_LOG_FILE");
+    std::env::set_var("RUST_LOG_TARGET", "printer");
+    let target_err = rustlog::try_init_from_env().unwrap_err();
+    std::env::remove_var("RUST_LOG_TARGET");
+    std::env::set_var("RUST_LOG_FORMAT", "xml");
+    let format_err = rustlog::try_init_from_env().unwrap_err();
+    std::env::remove_var("RUST_LOG_FORMAT");
+
+    assert!(
+        matches!(&file_err, EnvError::File { path, .. } if *path == dir),
+        "{file_err:?}"
+    );
+    assert!(std::error::Error::source(&file_err).is_some());
+    assert!(
+        matches!(&target_err, EnvError::Target { value, .. } if value == "printer"),
+        "{target_err:?}"
+    );
+    assert!(
+        matches!(&format_err, EnvError::Format { value, .. } if value == "xml"),
+        "{format_err:?}"
+    );
+}
@@ -0,0 +1,63 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, Eol};
+
+#[test]
+fn crlf_ends_records_and_banner() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_eol(Eol::CrLf);
+
+    info!("one");
+    rustlog::banner_with("app", "1.0");
+    rustlog::set_eol(Eol::Lf);
+    info!("two");
+
+    assert_eq!(rustlog::eol(), Eol::Lf);
+    let out = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(out, "INFO  one\r\napp v1.0\r\nINFO  two\n");
+}
+
+#[test]
+fn crlf_in_direct_write_mode() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_direct_write(true);
+    rustlog::set_eol(Eol::CrLf);
+
+    info!("multi\nline");
+
+    rustlog::set_eol(Eol::Lf);
+    rustlog::set_direct_write(false);
+    let out = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(out, "INFO  multi\nline\r\n");
+}
+
+#[test]
+fn eol_is_per_logger() {
+    use rustlog::local::{info as linfo, Logger};
+
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_eol(Eol::CrLf)
+        .build()
+        .unwrap();
+    let direct = lg.child();
+    direct.set_direct_write(true);
+    let lf = lg.child();
+    lf.set_eol(Eol::Lf);
+
+    linfo!(&lg, "a\nb");
+    linfo!(&direct, "c\nd");
+    linfo!(&lf, "e");
+
+    assert_eq!(mem.text(), "INFO  a\nb\r\nINFO  c\nd\r\nINFO  e\n");
+}
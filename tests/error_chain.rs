@@ -0,0 +1,47 @@
+mod helpers;
+use helpers::*;
+use rustlog::{error_chain, ErrorChain};
+use std::fmt;
+
+#[derive(Debug)]
+struct Timeout;
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+impl std::error::Error for Timeout {}
+
+#[derive(Debug)]
+struct ConnectFailed(Timeout);
+impl fmt::Display for ConnectFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("connect failed")
+    }
+}
+impl std::error::Error for ConnectFailed {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[test]
+fn chain_lists_every_source() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    let err = ConnectFailed(Timeout);
+    error_chain!(err);
+    error_chain!(err, "saving {}", 7);
+
+    assert_eq!(
+        lines_from(&buf),
+        [
+            "ERROR connect failed <- timed out",
+            "ERROR saving 7: connect failed <- timed out"
+        ]
+    );
+    assert_eq!(ErrorChain::new(&Timeout).to_string(), "timed out");
+}
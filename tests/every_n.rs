@@ -0,0 +1,39 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::info_every_n;
+use rustlog::local::Logger;
+
+#[test]
+fn every_100_of_250_emits_three_lines() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    for i in 0..250 {
+        info_every_n!(100, "progress {i}");
+    }
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(lines[0].ends_with("progress 0"));
+    assert!(lines[1].ends_with("progress 100"));
+    assert!(lines[2].ends_with("progress 200"));
+}
+
+#[test]
+fn local_every_n() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .build()?;
+
+    for i in 0..10 {
+        rustlog::local::warn_every_n!(&lg, 4, "tick {i}");
+    }
+    let s = mem.text();
+    let ends: Vec<_> = s.lines().map(|l| l.rsplit(' ').next().unwrap()).collect();
+    assert_eq!(ends, ["0", "4", "8"], "{s}");
+    Ok(())
+}
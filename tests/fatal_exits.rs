@@ -0,0 +1,43 @@
+use std::process::Command;
+
+const CHILD_ENV: &str = "RUSTLOG_FATAL_EXIT_CHILD";
+
+// Runs only inside the subprocess spawned by `fatal_exits_with_code`.
+#[test]
+fn fatal_exit_child() {
+    let Ok(path) = std::env::var(CHILD_ENV) else {
+        return;
+    };
+    rustlog::set_file(path).unwrap();
+    rustlog::set_fatal_exits(Some(3));
+    // records that are never written don't exit
+    rustlog::set_enabled(false);
+    rustlog::fatal!("while disabled");
+    rustlog::set_enabled(true);
+    rustlog::set_filter(Box::new(|r| r.group != Some("dropped")));
+    rustlog::fatal_group!("dropped", "filtered out");
+    rustlog::error!("still running");
+    rustlog::fatal!("giving up");
+    unreachable!("fatal! must exit the process");
+}
+
+#[test]
+fn fatal_exits_with_code() {
+    let mut path = std::env::temp_dir();
+    path.push(format!("rustlog_fatal_exit_{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let status = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "fatal_exit_child", "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, &path)
+        .status()
+        .unwrap();
+    assert_eq!(status.code(), Some(3));
+
+    let s = std::fs::read_to_string(&path).unwrap();
+    assert!(s.contains("still running"), "{s}");
+    assert!(!s.contains("while disabled") && !s.contains("filtered out"), "{s}");
+    assert!(s.lines().last().unwrap().ends_with("giving up"), "{s}");
+    let _ = std::fs::remove_file(&path);
+}
+
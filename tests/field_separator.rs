@@ -0,0 +1,48 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, info_group};
+
+#[test]
+fn pipe_separates_prefix_fields() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_field_separator(" | ");
+
+    info_group!("g1", "hello");
+    info!("plain");
+
+    rustlog::set_field_separator(" ");
+    info_group!("g1", "back");
+
+    assert_eq!(rustlog::field_separator(), " ");
+    assert_eq!(
+        lines_from(&buf),
+        ["INFO  | [g1] | hello", "INFO  | plain", "INFO  [g1] back"]
+    );
+}
+
+#[test]
+fn separator_is_per_logger() {
+    use rustlog::local::{info_group as linfo_group, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_field_separator(" | ")
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_field_separator(" ");
+
+    linfo_group!(&lg, "db", "parent");
+    linfo_group!(&child, "db", "child");
+
+    assert_eq!(lg.field_separator(), " | ");
+    assert_eq!(cap.lines(), ["INFO  | [db] | parent", "INFO  [db] child"]);
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+use rustlog::{info, ColorMode};
+
+#[test]
+fn osc8_link_only_when_enabled_and_colored() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(true);
+    rustlog::set_color_mode(ColorMode::Always);
+
+    info!("plain");
+    rustlog::set_file_line_hyperlink(true);
+    info!("linked");
+    rustlog::set_color_mode(ColorMode::Never);
+    info!("no color");
+    rustlog::set_file_line_hyperlink(false);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 3);
+    assert!(!lines[0].contains("\x1b]8;;"), "{:?}", lines[0]);
+    assert!(lines[1].contains("\x1b]8;;file:///"), "{:?}", lines[1]);
+    let target = format!("/{}#L", file!().replace('\\', "/"));
+    assert!(lines[1].contains(&target), "{:?}", lines[1]);
+    assert!(lines[1].contains(&format!("{}:", file!())), "{:?}", lines[1]);
+    assert!(lines[1].contains("\x1b]8;;\x1b\\>"), "{:?}", lines[1]);
+    assert!(!lines[2].contains("\x1b]8;;"), "{:?}", lines[2]);
+    assert!(lines[2].contains(&format!("<{}:", file!())), "{:?}", lines[2]);
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::path::PathBuf;
+
+use rustlog::local::{info as linfo, Logger};
+use rustlog::FileOptions;
+
+fn temp_path(name: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!("rustlog_{name}_{}", std::process::id()));
+    p
+}
+
+#[test]
+fn truncate_starts_with_an_empty_file() {
+    let p = temp_path("truncate.log");
+    fs::write(&p, "previous run\n").unwrap();
+
+    let opts = FileOptions {
+        append: false,
+        truncate: true,
+        ..FileOptions::default()
+    };
+    rustlog::set_file_with(&p, opts).unwrap();
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+    rustlog::info!("fresh");
+    rustlog::reset_target();
+
+    assert_eq!(fs::read_to_string(&p).unwrap(), "INFO  fresh\n");
+
+    // the default still appends
+    rustlog::set_file(&p).unwrap();
+    rustlog::info!("again");
+    rustlog::reset_target();
+    assert_eq!(fs::read_to_string(&p).unwrap(), "INFO  fresh\nINFO  again\n");
+    let _ = fs::remove_file(&p);
+}
+
+#[test]
+fn create_dirs_makes_missing_parents() {
+    let root = temp_path("dirs");
+    let _ = fs::remove_dir_all(&root);
+    let p = root.join("a").join("b").join("app.log");
+
+    let plain = Logger::builder().file(&p).build();
+    assert!(plain.is_err(), "parent does not exist yet");
+
+    let opts = FileOptions {
+        create_dirs: true,
+        ..FileOptions::default()
+    };
+    let lg = Logger::builder()
+        .file_with(&p, opts)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()
+        .unwrap();
+    linfo!(&lg, "nested");
+
+    assert_eq!(fs::read_to_string(&p).unwrap(), "INFO  nested\n");
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,47 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, info_group, Level};
+
+#[test]
+fn filter_drops_noisy_group() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_show_group(false); // the filter still sees the group
+
+    rustlog::set_filter(Box::new(|r| {
+        r.group != Some("noisy") && !r.args.to_string().contains("secret")
+    }));
+    info_group!("noisy", "dropped");
+    info_group!("net", "kept");
+    info!("token secret dropped");
+    info!("plain kept");
+    rustlog::clear_filter();
+    info_group!("noisy", "back");
+    rustlog::set_show_group(true);
+
+    assert_eq!(
+        lines_from(&buf),
+        ["INFO  kept", "INFO  plain kept", "INFO  back"]
+    );
+}
+
+#[test]
+fn filter_runs_after_level_check() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_level(Level::Info);
+
+    rustlog::set_filter(Box::new(|r| {
+        assert!(r.level >= Level::Info, "filter saw a disabled record");
+        true
+    }));
+    rustlog::debug!("below level");
+    info!("shown");
+    rustlog::clear_filter();
+
+    assert_eq!(lines_from(&buf), ["INFO  shown"]);
+}
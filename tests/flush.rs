@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use rustlog::local::Logger;
+
+struct Flag(Arc<AtomicBool>);
+impl Write for Flag {
+    fn write(&mut self, b: &[u8]) -> io::Result<usize> {
+        Ok(b.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[test]
+fn global_flush_reaches_writer() -> io::Result<()> {
+    let flushed = Arc::new(AtomicBool::new(false));
+    rustlog::set_writer(Box::new(Flag(flushed.clone())));
+    rustlog::info!("buffered");
+    assert!(!flushed.load(Ordering::SeqCst));
+
+    rustlog::flush()?;
+    assert!(flushed.load(Ordering::SeqCst));
+
+    rustlog::reset_target();
+    rustlog::flush()
+}
+
+#[test]
+fn logger_flush_reaches_writer() -> io::Result<()> {
+    let flushed = Arc::new(AtomicBool::new(false));
+    let lg = Logger::builder()
+        .set_writer(Box::new(Flag(flushed.clone())))
+        .build()?;
+    rustlog::local::info!(&lg, "buffered");
+    assert!(!flushed.load(Ordering::SeqCst));
+
+    lg.flush()?;
+    assert!(flushed.load(Ordering::SeqCst));
+    Ok(())
+}
@@ -0,0 +1,53 @@
+mod helpers;
+use helpers::*;
+
+use rustlog::local::Logger;
+use rustlog::{ColorMode, Level, Target};
+
+#[test]
+fn global_getters_reflect_setters() {
+    let _g = test_lock().lock().unwrap();
+    let (_buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    for on in [true, false] {
+        rustlog::set_show_time(on);
+        rustlog::set_show_thread_id(on);
+        rustlog::set_show_group(on);
+        rustlog::set_show_file_line(on);
+        assert_eq!(rustlog::show_time(), on);
+        assert_eq!(rustlog::show_thread_id(), on);
+        assert_eq!(rustlog::show_group(), on);
+        assert_eq!(rustlog::show_file_line(), on);
+    }
+    for m in [ColorMode::Always, ColorMode::Never, ColorMode::Auto] {
+        rustlog::set_color_mode(m);
+        assert_eq!(rustlog::color_mode(), m);
+    }
+    assert_eq!(rustlog::target(), Target::Writer);
+}
+
+#[test]
+fn logger_getters_reflect_builder_and_setters() -> std::io::Result<()> {
+    let lg = Logger::builder()
+        .set_level(Level::Warn)
+        .set_show_time(false)
+        .set_show_group(false)
+        .set_color_mode(ColorMode::Never)
+        .stdout()
+        .build()?;
+
+    assert_eq!(lg.level(), Level::Warn);
+    assert!(!lg.show_time());
+    assert!(!lg.show_group());
+    assert_eq!(lg.color_mode(), ColorMode::Never);
+    assert_eq!(lg.target(), Target::Stdout);
+
+    lg.set_show_thread_id(true);
+    lg.set_show_file_line(true);
+    lg.set_target(Target::Stderr);
+    assert!(lg.show_thread_id());
+    assert!(lg.show_file_line());
+    assert_eq!(lg.target(), Target::Stderr);
+    Ok(())
+}
@@ -0,0 +1,29 @@
+mod helpers;
+use helpers::*;
+use rustlog::local::info as linfo;
+use rustlog::{info, info_group, Level};
+
+#[test]
+fn global_logger_matches_free_functions() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    let lg = rustlog::global();
+    lg.set_level(Level::Warn);
+    assert_eq!(rustlog::level(), Level::Warn);
+    rustlog::set_level(Level::Info);
+    assert_eq!(lg.level(), Level::Info);
+    assert_eq!(lg.target(), rustlog::target());
+
+    info!("root {}", 1);
+    linfo!(lg, "root {}", 1);
+    info_group!("net", "grouped");
+    rustlog::local::info_group!(lg, "net", "grouped");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], lines[1]);
+    assert_eq!(lines[2], lines[3]);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+use rustlog::{info_group, ColorMode, GroupColorMode};
+
+#[test]
+fn group_tag_without_bold_keeps_its_color() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_color_mode(ColorMode::Always);
+
+    info_group!("db", "bold");
+    rustlog::set_group_bold(false);
+    info_group!("db", "plain");
+    rustlog::set_group_color_mode(GroupColorMode::ByName);
+    info_group!("db", "by name");
+    rustlog::set_group_color_mode(GroupColorMode::ByLevel);
+    rustlog::set_group_bold(true);
+    rustlog::set_color_mode(ColorMode::Never);
+
+    assert!(rustlog::group_bold());
+    let lines = lines_from(&buf);
+    assert!(lines[0].contains("[\x1b[1m\x1b[32mdb\x1b[0m]"), "{:?}", lines[0]);
+    assert!(lines[1].contains("[\x1b[32mdb\x1b[0m]"), "{:?}", lines[1]);
+    assert!(lines[2].contains("[\x1b[38;2;99;102;108mdb\x1b[0m]"), "{:?}", lines[2]);
+    assert!(lines[1..].iter().all(|l| !l.contains("\x1b[1m")));
+}
+
+#[test]
+fn bold_is_per_logger() {
+    use rustlog::local::{info_group as linfo_group, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_color_mode(ColorMode::Always)
+        .set_group_bold(false)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_group_bold(true);
+
+    linfo_group!(&lg, "db", "parent");
+    linfo_group!(&child, "db", "child");
+
+    let lines = cap.lines();
+    assert!(lines[0].contains("[\x1b[32mdb\x1b[0m]"), "{:?}", lines[0]);
+    assert!(lines[1].contains("[\x1b[1m\x1b[32mdb\x1b[0m]"), "{:?}", lines[1]);
+}
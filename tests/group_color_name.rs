@@ -0,0 +1,64 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+use rustlog::{info_group, warn_group, ColorMode, GroupColorMode};
+
+fn group_escape(line: &str) -> &str {
+    let start = line.find("\x1b[38;2;").unwrap();
+    let end = start + line[start..].find('m').unwrap() + 1;
+    &line[start..end]
+}
+
+#[test]
+fn by_name_is_stable_per_name_and_differs_between_names() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_color_mode(ColorMode::Always);
+    rustlog::set_group_color_mode(GroupColorMode::ByName);
+
+    info_group!("db", "a");
+    warn_group!("db", "b");
+    info_group!("net", "c");
+
+    rustlog::set_group_color_mode(GroupColorMode::ByLevel);
+    info_group!("db", "d");
+    rustlog::set_color_mode(ColorMode::Never);
+    info_group!("db", "e");
+
+    let lines = lines_from(&buf);
+    // independent of the level, and of the process (FNV-1a, not a seeded hasher)
+    assert_eq!(group_escape(&lines[0]), group_escape(&lines[1]));
+    assert_eq!(group_escape(&lines[0]), "\x1b[38;2;99;102;108m");
+    assert_ne!(group_escape(&lines[0]), group_escape(&lines[2]));
+    assert!(lines[0].contains("db\x1b[0m]"), "{:?}", lines[0]);
+    assert!(!lines[3].contains("\x1b[38;2;"), "{:?}", lines[3]);
+    assert_eq!(lines[4], "INFO  [db] e");
+}
+
+#[test]
+fn mode_is_per_logger() {
+    use rustlog::local::{info_group as linfo_group, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_color_mode(ColorMode::Always)
+        .set_group_color_mode(GroupColorMode::ByName)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_group_color_mode(GroupColorMode::ByLevel);
+
+    linfo_group!(&lg, "db", "parent");
+    linfo_group!(&child, "db", "child");
+
+    let lines = cap.lines();
+    assert_eq!(group_escape(&lines[0]), "\x1b[38;2;99;102;108m");
+    assert!(!lines[1].contains("\x1b[38;2;"), "{:?}", lines[1]);
+}
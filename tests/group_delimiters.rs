@@ -0,0 +1,57 @@
+mod helpers;
+use helpers::*;
+use rustlog::info_group;
+
+#[test]
+fn angle_brackets_wrap_the_group() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_group_delimiters("<", ">");
+    assert_eq!(rustlog::group_delimiters(), ("<", ">"));
+
+    info_group!("net", "up");
+    rustlog::set_color_mode(rustlog::ColorMode::Always);
+    info_group!("net", "colored");
+    rustlog::set_color_mode(rustlog::ColorMode::Never);
+
+    rustlog::set_group_delimiters("[", "]");
+    info_group!("net", "back");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines[0], "INFO  <net> up");
+    #[cfg(feature = "color")]
+    assert!(
+        lines[1].contains("<\x1b[1m\x1b[32mnet\x1b[0m>"),
+        "{:?}",
+        lines[1]
+    );
+    #[cfg(not(feature = "color"))]
+    assert_eq!(lines[1], "INFO  <net> colored");
+    assert_eq!(lines[2], "INFO  [net] back");
+}
+
+#[test]
+fn delimiters_are_per_logger() {
+    use rustlog::local::{info_group as linfo_group, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_group_delimiters("{", "}")
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_group_delimiters("(", ")");
+
+    linfo_group!(&lg, "db", "parent");
+    linfo_group!(&child, "db", "child");
+
+    assert_eq!(lg.group_delimiters(), ("{", "}"));
+    assert_eq!(cap.lines(), ["INFO  {db} parent", "INFO  (db) child"]);
+}
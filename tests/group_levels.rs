@@ -0,0 +1,113 @@
+mod helpers;
+use helpers::*;
+use rustlog::{ct_enabled, debug_group, info_group, trace_group, Level};
+
+#[test]
+fn longest_prefix_wins_and_unmatched_falls_back() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_level(Level::Info);
+    rustlog::set_group_level("net", Level::Debug);
+    rustlog::set_group_level("net::http", Level::Trace);
+    rustlog::set_group_level("db", Level::Error);
+
+    trace_group!("net", "net trace");
+    debug_group!("net", "net debug");
+    trace_group!("net::http", "http trace");
+    trace_group!("net::tcp", "tcp trace");
+    debug_group!("net::tcp", "tcp debug");
+    info_group!("db", "db info");
+    debug_group!("network", "network debug");
+    info_group!("other", "other info");
+
+    rustlog::clear_group_levels();
+    debug_group!("net", "cleared debug");
+
+    let text = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    // release builds compile Trace/Debug records out
+    let debug = ct_enabled(Level::Debug);
+    let trace = ct_enabled(Level::Trace);
+    assert!(!text.contains("net trace"), "{text}");
+    assert_eq!(text.contains("net debug"), debug, "{text}");
+    assert_eq!(text.contains("http trace"), trace, "{text}");
+    assert!(!text.contains("tcp trace"), "{text}");
+    assert_eq!(
+        text.contains("tcp debug"),
+        debug,
+        "inherits `net` rule: {text}"
+    );
+    assert!(!text.contains("db info"), "{text}");
+    assert!(!text.contains("network debug"), "not a `::` prefix: {text}");
+    assert!(text.contains("other info"), "global fallback: {text}");
+    assert!(!text.contains("cleared debug"), "{text}");
+}
+
+#[test]
+fn rust_log_directives_are_parsed() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    std::env::set_var("RUST_LOG_LEVEL", "error");
+    std::env::set_var("RUST_LOG", "warn, net=debug ,bogus=loud,net::http=trace");
+    rustlog::init_from_env();
+    std::env::remove_var("RUST_LOG");
+    std::env::remove_var("RUST_LOG_LEVEL");
+
+    assert_eq!(rustlog::level(), Level::Warn, "bare directive beats RUST_LOG_LEVEL");
+    debug_group!("net", "net debug");
+    trace_group!("net::http", "http trace");
+    info_group!("bogus", "bogus info");
+    rustlog::clear_group_levels();
+
+    let text = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        text.contains("net debug"),
+        ct_enabled(Level::Debug),
+        "{text}"
+    );
+    assert_eq!(
+        text.contains("http trace"),
+        ct_enabled(Level::Trace),
+        "{text}"
+    );
+    assert!(!text.contains("bogus info"), "invalid directive is ignored: {text}");
+}
+
+#[test]
+fn group_enabled_follows_global_and_group_levels() {
+    let _g = test_lock().lock().unwrap();
+    reset_runtime();
+    rustlog::clear_group_levels();
+    rustlog::set_level(Level::Warn);
+    rustlog::set_group_level("net", Level::Debug);
+    // the global checks include the compile-time floor, which drops Trace/Debug
+    // in release builds
+    let debug = ct_enabled(Level::Debug);
+    assert_eq!(rustlog::group_enabled("net", Level::Debug), debug);
+    assert_eq!(rustlog::group_enabled("net::tcp", Level::Debug), debug);
+    assert!(!rustlog::group_enabled("net", Level::Trace));
+    assert!(!rustlog::group_enabled("db", Level::Info));
+    assert!(rustlog::group_enabled("db", Level::Warn));
+
+    rustlog::set_level(Level::Trace);
+    assert!(rustlog::group_enabled("db", Level::Info));
+    assert!(!rustlog::group_enabled("net", Level::Trace));
+    rustlog::clear_group_levels();
+    assert_eq!(
+        rustlog::group_enabled("net", Level::Trace),
+        ct_enabled(Level::Trace)
+    );
+
+    rustlog::set_enabled(false);
+    assert!(!rustlog::group_enabled("net", Level::Fatal));
+    rustlog::set_enabled(true);
+    reset_runtime();
+
+    let lg = rustlog::local::Logger::default();
+    lg.set_level(Level::Error);
+    lg.set_group_level("net", Level::Info);
+    assert!(lg.group_enabled("net", Level::Info));
+    assert!(!lg.group_enabled("db", Level::Info));
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "gzip")]
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use flate2::read::MultiGzDecoder;
+use rustlog::local::{info as linfo, Logger};
+
+fn temp_file(tag: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "rustlog_gzip_{tag}_{}_{}.log.gz",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ))
+}
+
+// Decodes as much as is readable; a stream that was only flushed has no trailer yet
+fn gunzip(p: &Path) -> String {
+    let mut dec = MultiGzDecoder::new(fs::File::open(p).unwrap());
+    let mut out = Vec::new();
+    let mut chunk = [0; 256];
+    while let Ok(n @ 1..) = dec.read(&mut chunk) {
+        out.extend_from_slice(&chunk[..n]);
+    }
+    String::from_utf8(out).unwrap()
+}
+
+fn logger(p: &Path) -> std::io::Result<Logger> {
+    Logger::builder()
+        .gzip_file(p)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()
+}
+
+#[test]
+fn lines_round_trip_after_flush_and_drop() -> std::io::Result<()> {
+    let path = temp_file("roundtrip");
+
+    let lg = logger(&path)?;
+    linfo!(&lg, "one");
+    linfo!(&lg, "two");
+    lg.flush()?;
+    assert_eq!(gunzip(&path), "INFO  one\nINFO  two\n");
+    linfo!(&lg, "three");
+    drop(lg);
+    assert_eq!(gunzip(&path), "INFO  one\nINFO  two\nINFO  three\n");
+
+    // reopening appends a second gzip member
+    let lg = logger(&path)?;
+    linfo!(&lg, "four");
+    drop(lg);
+    let mut all = String::new();
+    MultiGzDecoder::new(fs::File::open(&path)?).read_to_string(&mut all)?;
+    assert!(all.ends_with("INFO  three\nINFO  four\n"), "{all:?}");
+
+    fs::remove_file(&path)
+}
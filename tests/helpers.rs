@@ -15,8 +15,10 @@ fn shared_buf() -> &'static Arc<Mutex<Vec<u8>>> {
     BUF.get_or_init(|| Arc::new(Mutex::new(Vec::new())))
 }
 
-#[derive(Clone)]
-struct Mem(Arc<Mutex<Vec<u8>>>);
+/// In-memory writer; clones share one buffer, so keep a clone to read back what
+/// was handed to `set_writer`
+#[derive(Clone, Default)]
+pub struct Mem(pub Arc<Mutex<Vec<u8>>>);
 impl Write for Mem {
     fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
         self.0.lock().unwrap().extend_from_slice(bytes);
@@ -26,6 +28,19 @@ impl Write for Mem {
         Ok(())
     }
 }
+impl Mem {
+    /// Everything written so far
+    /// # Panics
+    #[must_use]
+    pub fn text(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+    /// Everything written so far, split into lines
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        lines_from(&self.0)
+    }
+}
 
 struct Guard;
 impl Drop for Guard {
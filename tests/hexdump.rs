@@ -0,0 +1,45 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, HexDump};
+
+#[test]
+fn short_slice_layout() {
+    assert_eq!(
+        HexDump(b"Hello\nworld\n").to_string(),
+        "00000000  48 65 6c 6c 6f 0a 77 6f  72 6c 64 0a              |Hello.world.|"
+    );
+}
+
+#[test]
+fn rows_offsets_and_empty_input() {
+    let bytes: Vec<u8> = (0u8..20).collect();
+    let dump = HexDump(&bytes).to_string();
+    let rows: Vec<_> = dump.lines().collect();
+
+    assert_eq!(
+        rows,
+        [
+            "00000000  00 01 02 03 04 05 06 07  08 09 0a 0b 0c 0d 0e 0f  |................|",
+            "00000010  10 11 12 13                                       |....|"
+        ]
+    );
+    assert_eq!(HexDump(&[]).to_string(), "");
+}
+
+#[test]
+fn usable_in_log_macros() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    info!("packet:\n{}", HexDump(b"AB"));
+
+    assert_eq!(
+        lines_from(&buf),
+        [
+            "INFO  packet:",
+            "00000000  41 42                                             |AB|"
+        ]
+    );
+}
@@ -0,0 +1,98 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, scope};
+
+fn message_indent(line: &str) -> usize {
+    let rest = line.strip_prefix("INFO ").unwrap();
+    rest.len() - rest.trim_start().len()
+}
+
+#[test]
+fn nested_scopes_indent_deeper() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    info!("top");
+    {
+        let _outer = rustlog::indent_guard();
+        info!("outer");
+        {
+            let _inner = rustlog::indent_guard();
+            info!("inner");
+        }
+        info!("outer again");
+    }
+    scope!({ info!("block") });
+
+    let lines = lines_from(&buf);
+    assert_eq!(
+        lines,
+        [
+            "INFO  top",
+            "INFO    outer",
+            "INFO      inner",
+            "INFO    outer again",
+            "INFO    block"
+        ]
+    );
+    assert!(message_indent(&lines[2]) > message_indent(&lines[1]));
+}
+
+#[test]
+fn indent_width_is_configurable() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_indent_width(4);
+
+    scope!({ info!("wide") });
+    rustlog::set_indent_width(2);
+
+    assert_eq!(lines_from(&buf), ["INFO      wide"]);
+}
+
+#[test]
+fn depth_is_restored_after_a_panic() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    let res = std::panic::catch_unwind(|| {
+        scope!();
+        scope!();
+        panic!("boom");
+    });
+    info!("after");
+
+    assert!(res.is_err());
+    assert_eq!(lines_from(&buf), ["INFO  after"]);
+}
+
+#[test]
+fn width_is_per_logger() {
+    use rustlog::local::{info as linfo, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_indent_width(4)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_indent_width(1);
+
+    scope!({
+        linfo!(&lg, "parent");
+        linfo!(&child, "child");
+    });
+
+    assert_eq!(cap.lines(), ["INFO      parent", "INFO   child"]);
+}
@@ -0,0 +1,26 @@
+mod helpers;
+use helpers::*;
+
+use std::io::BufWriter;
+
+#[test]
+fn guard_flushes_buffered_writer_on_drop() {
+    let guard = rustlog::init();
+    // a second call configures nothing (no second panic hook) and is harmless
+    let again = rustlog::try_init().unwrap();
+    drop(again);
+
+    let mem = Mem::default();
+    rustlog::set_writer(Box::new(BufWriter::new(mem.clone())));
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+    rustlog::info!("buffered");
+    // copy out: a failing assert must not hold the lock while the panic hook logs
+    let early = mem.text();
+    assert!(early.is_empty(), "{early:?}");
+
+    drop(guard);
+    assert_eq!(mem.text(), "INFO  buffered\n");
+    rustlog::reset_target();
+}
@@ -0,0 +1,30 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::local::Logger;
+use rustlog::Level;
+
+#[test]
+fn root_macros_use_the_installed_builder_config() -> io::Result<()> {
+    let mem = Mem::default();
+    Logger::builder()
+        .set_level(Level::Warn)
+        .set_writer(Box::new(mem.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .default_group("app")
+        .with_panic_hook(true)
+        .install_global()?;
+
+    rustlog::info!("filtered");
+    rustlog::warn!("kept");
+
+    assert_eq!(rustlog::level(), Level::Warn);
+    assert_eq!(rustlog::target(), rustlog::Target::Writer);
+    assert_eq!(mem.text(), "WARN  [app] kept\n");
+    rustlog::reset_target();
+    Ok(())
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "json")]
+mod helpers;
+use helpers::*;
+use rustlog::{info_group, DefaultFormat, JsonFormat};
+
+/// Minimal validator: a flat JSON object of string/number values.
+/// Returns the decoded key/value pairs in order.
+fn parse_flat_object(s: &str) -> Option<Vec<(String, String)>> {
+    fn string(it: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+        if it.next()? != '"' {
+            return None;
+        }
+        let mut out = String::new();
+        loop {
+            match it.next()? {
+                '"' => return Some(out),
+                '\\' => match it.next()? {
+                    '"' => out.push('"'),
+                    '\\' => out.push('\\'),
+                    '/' => out.push('/'),
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'u' => {
+                        let hex: String = (0..4).map(|_| it.next()).collect::<Option<_>>()?;
+                        out.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                    }
+                    _ => return None,
+                },
+                c if (c as u32) < 0x20 => return None,
+                c => out.push(c),
+            }
+        }
+    }
+    let mut it = s.chars().peekable();
+    if it.next()? != '{' {
+        return None;
+    }
+    let mut pairs = Vec::new();
+    loop {
+        let key = string(&mut it)?;
+        if it.next()? != ':' {
+            return None;
+        }
+        let val = if it.peek() == Some(&'"') {
+            string(&mut it)?
+        } else {
+            let mut n = String::new();
+            while it.peek().is_some_and(char::is_ascii_digit) {
+                n.push(it.next()?);
+            }
+            if n.is_empty() {
+                return None;
+            }
+            n
+        };
+        pairs.push((key, val));
+        match it.next()? {
+            ',' => {}
+            '}' => return it.next().is_none().then_some(pairs),
+            _ => return None,
+        }
+    }
+}
+
+#[test]
+fn json_lines_are_valid_and_escaped() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(true);
+    rustlog::set_format(Box::new(JsonFormat));
+
+    info_group!("n\"et", "say \"hi\"\nnext\tline \u{1}");
+
+    let lines = lines_from(&buf);
+    rustlog::set_format(Box::new(DefaultFormat));
+    assert_eq!(lines.len(), 1, "one JSON object per line: {lines:?}");
+    let pairs = parse_flat_object(&lines[0]).unwrap_or_else(|| panic!("invalid JSON: {}", lines[0]));
+    let keys: Vec<&str> = pairs.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["level", "group", "file", "line", "msg"]);
+    assert_eq!(pairs[0].1, "info");
+    assert_eq!(pairs[1].1, "n\"et");
+    assert_eq!(pairs[2].1, file!());
+    assert_eq!(pairs[4].1, "say \"hi\"\nnext\tline \u{1}");
+}
+
+#[test]
+fn json_omits_disabled_fields() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_show_group(false);
+    rustlog::set_format(Box::new(JsonFormat));
+
+    info_group!("net", "plain");
+
+    let lines = lines_from(&buf);
+    rustlog::set_format(Box::new(DefaultFormat));
+    rustlog::set_show_group(true);
+    assert_eq!(lines, [r#"{"level":"info","msg":"plain"}"#]);
+}
+
+#[test]
+fn json_fields_become_keys() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_format(Box::new(JsonFormat));
+
+    rustlog::info_kv!("done", status = 200, path = "/a \"b\"");
+
+    let lines = lines_from(&buf);
+    rustlog::set_format(Box::new(DefaultFormat));
+    assert_eq!(
+        lines,
+        [r#"{"level":"info","msg":"done","status":"200","path":"/a \"b\""}"#]
+    );
+}
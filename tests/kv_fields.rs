@@ -0,0 +1,48 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::local::Logger;
+use rustlog::{info_kv, warn_kv};
+
+#[test]
+fn fields_render_in_order_with_quoting() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    let user = "ann lee";
+    info_kv!("request done", status = 200, path = "/x", user = user);
+    warn_kv!("no fields");
+    info_kv!("empty and quotes", empty = "", quoted = "say \"hi\"",);
+
+    let lines = lines_from(&buf);
+    assert_eq!(
+        lines[0],
+        r#"INFO  request done status=200 path=/x user="ann lee""#
+    );
+    assert_eq!(lines[1], "WARN  no fields");
+    assert_eq!(
+        lines[2],
+        r#"INFO  empty and quotes empty="" quoted="say \"hi\"""#
+    );
+}
+
+#[test]
+fn local_fields() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+
+    let ms = 12.5;
+    rustlog::local::info_kv!(&lg, "done", took_ms = ms, ok = true);
+    let s = mem.text();
+    assert_eq!(s, "INFO  done took_ms=12.5 ok=true\n");
+    Ok(())
+}
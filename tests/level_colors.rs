@@ -0,0 +1,52 @@
+#![cfg(feature = "color")]
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::local::Logger;
+use rustlog::{info, warn, ColorMode, Level};
+
+const ORANGE: &str = "\x1b[38;5;208m";
+
+#[test]
+fn custom_color_applies_to_its_level_only() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_color_mode(ColorMode::Always);
+    rustlog::set_level_color(Level::Warn, ORANGE);
+
+    warn!("careful");
+    info!("fine");
+
+    let lines = lines_from(&buf);
+    assert!(lines[0].starts_with(&format!("{ORANGE}WARN ")), "{:?}", lines[0]);
+    assert!(!lines[1].contains(ORANGE), "{:?}", lines[1]);
+    assert!(lines[1].starts_with("\x1b[32mINFO "), "{:?}", lines[1]);
+
+    rustlog::set_level_color(Level::Warn, "\x1b[33m");
+    rustlog::set_color_mode(ColorMode::Auto);
+}
+
+#[test]
+fn builder_level_color() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .set_color_mode(ColorMode::Always)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_level_color(Level::Info, ORANGE)
+        .build()?;
+
+    rustlog::local::info!(&lg, "themed");
+    rustlog::local::warn!(&lg, "default");
+
+    let s = mem.text();
+    let lines: Vec<_> = s.lines().collect();
+    assert!(lines[0].starts_with(&format!("{ORANGE}INFO ")), "{s:?}");
+    assert!(lines[1].starts_with("\x1b[33mWARN "), "{s:?}");
+    Ok(())
+}
@@ -0,0 +1,33 @@
+mod helpers;
+use helpers::*;
+use rustlog::local::Logger;
+use rustlog::{info, Level};
+
+#[test]
+fn with_level_restores_previous_level() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_level(Level::Warn);
+
+    {
+        let _lvl = rustlog::with_level(Level::Info);
+        assert_eq!(rustlog::level(), Level::Info);
+        info!("inside");
+    }
+    assert_eq!(rustlog::level(), Level::Warn);
+    info!("outside");
+
+    assert_eq!(lines_from(&buf), ["INFO  inside"]);
+}
+
+#[test]
+fn logger_with_level_restores_previous_level() {
+    let lg = Logger::builder().set_level(Level::Warn).build().unwrap();
+    {
+        let _lvl = lg.with_level(Level::Trace);
+        assert!(lg.enabled(Level::Trace, None));
+    }
+    assert_eq!(lg.level(), Level::Warn);
+}
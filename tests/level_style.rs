@@ -0,0 +1,65 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, warn, LevelStyle};
+
+fn styled_lines(style: LevelStyle) -> Vec<String> {
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_level_style(style);
+    info!("hello");
+    warn!("careful");
+    rustlog::set_level_style(LevelStyle::Full);
+    lines_from(&buf)
+}
+
+#[test]
+fn short_style_is_single_letter() {
+    let _g = test_lock().lock().unwrap();
+    let lines = styled_lines(LevelStyle::Short);
+
+    assert_eq!(lines[0].split_whitespace().next(), Some("I"), "{lines:?}");
+    assert_eq!(lines[0], "I hello");
+    assert_eq!(lines[1], "W careful");
+}
+
+#[test]
+fn lower_style_is_lowercase_and_padded() {
+    let _g = test_lock().lock().unwrap();
+    let lines = styled_lines(LevelStyle::Lower);
+
+    assert_eq!(lines[0], "info  hello");
+    assert_eq!(lines[1], "warn  careful");
+}
+
+#[test]
+fn full_style_is_the_default() {
+    let _g = test_lock().lock().unwrap();
+    assert_eq!(rustlog::level_style(), LevelStyle::Full);
+    let lines = styled_lines(LevelStyle::Full);
+
+    assert_eq!(lines[0], "INFO  hello");
+}
+
+#[test]
+fn style_is_per_logger() {
+    use rustlog::local::{info as linfo, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_level_style(LevelStyle::Short)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_level_style(LevelStyle::Lower);
+
+    linfo!(&lg, "parent");
+    linfo!(&child, "child");
+
+    assert_eq!(cap.lines(), ["I parent", "info  child"]);
+}
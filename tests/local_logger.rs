@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 
 use rustlog::local::debug as ldebug;
 use rustlog::local::info as linfo;
+use rustlog::local::warn as lwarn;
 use rustlog::local::Logger;
 use rustlog::{Level, Target};
 
@@ -89,3 +90,49 @@ fn default_and_local_coexist_and_use_own_settings() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn default_group_tags_ungrouped_records() -> io::Result<()> {
+    let (writer, buf) = Mem::new();
+    let lg = Logger::builder()
+        .set_writer(writer)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .default_group("db")
+        .build()?;
+
+    linfo!(&lg, "connected");
+    rustlog::local::info_group!(&lg, "net", "explicit wins");
+    lg.set_default_group(None);
+    linfo!(&lg, "plain");
+
+    let s = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let lines: Vec<_> = s.lines().collect();
+    assert_eq!(lines, ["INFO  [db] connected", "INFO  [net] explicit wins", "INFO  plain"]);
+    Ok(())
+}
+
+#[test]
+fn builder_short_aliases_match_set_methods() -> io::Result<()> {
+    let (writer, buf) = Mem::new();
+    let lg = Logger::builder()
+        .writer(writer)
+        .level(Level::Warn)
+        .show_time(false)
+        .show_thread_id(false)
+        .show_group(false)
+        .show_file_line(false)
+        .color_mode(rustlog::ColorMode::Never)
+        .build()?;
+
+    assert_eq!(lg.level(), Level::Warn);
+    assert_eq!(lg.target(), Target::Writer);
+    lwarn!(&lg, "aliased");
+    rustlog::local::error_group!(&lg, "net", "no group");
+
+    let s = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    let lines: Vec<_> = s.lines().collect();
+    assert_eq!(lines, ["WARN  aliased", "ERROR no group"]);
+    Ok(())
+}
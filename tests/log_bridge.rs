@@ -0,0 +1,26 @@
+#![cfg(feature = "log-compat")]
+mod helpers;
+use helpers::*;
+use rustlog::Level;
+
+#[test]
+fn log_records_use_target_as_group() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::init_log_bridge().unwrap();
+    assert!(rustlog::init_log_bridge().is_err(), "second install must fail");
+
+    log::info!(target: "hyper::client", "connected to {}", "db");
+    rustlog::set_group_level("hyper", Level::Warn);
+    log::info!(target: "hyper::client", "filtered");
+    log::error!(target: "hyper::client", "boom");
+    rustlog::clear_group_levels();
+
+    let lines = lines_from(&buf);
+    assert_eq!(
+        lines,
+        ["INFO  [hyper::client] connected to db", "ERROR [hyper::client] boom"]
+    );
+}
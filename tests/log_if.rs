@@ -0,0 +1,49 @@
+mod helpers;
+use helpers::*;
+use rustlog::{error_if, info_if, warn_if};
+use std::cell::Cell;
+
+#[test]
+fn arguments_are_not_evaluated_when_false() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    let calls = Cell::new(0);
+    let costly = || {
+        calls.set(calls.get() + 1);
+        "costly"
+    };
+    warn_if!(false, "skipped {}", costly());
+    warn_if!(1 + 1 == 2, "kept {}", costly());
+    info_if!(calls.get() > 5, "never");
+    error_if!(true, "always");
+
+    assert_eq!(calls.get(), 1);
+    assert_eq!(lines_from(&buf), ["WARN  kept costly", "ERROR always"]);
+}
+
+#[test]
+fn local_variants() -> std::io::Result<()> {
+    use rustlog::local::{info_if as linfo_if, Logger};
+
+    let cap = rustlog::sink::CaptureSink::new(4);
+    let lg = Logger::builder()
+        .writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_file_line(false)
+        .set_show_thread_id(false)
+        .build()?;
+    let evaluated = Cell::new(false);
+
+    linfo_if!(&lg, false, "{}", {
+        evaluated.set(true);
+        "x"
+    });
+    linfo_if!(&lg, true, "yes");
+
+    assert!(!evaluated.get());
+    assert_eq!(cap.lines(), ["INFO  yes"]);
+    Ok(())
+}
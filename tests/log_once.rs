@@ -0,0 +1,50 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::local::Logger;
+use rustlog::{info_once, warn_once, Level};
+
+#[test]
+fn warn_once_in_loop_emits_one_line() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    for i in 0..100 {
+        warn_once!("dep {i}");
+    }
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 1, "{lines:?}");
+    assert!(lines[0].ends_with("dep 0"), "{}", lines[0]);
+}
+
+#[test]
+fn once_is_not_consumed_while_filtered() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    for l in [Level::Warn, Level::Info, Level::Info] {
+        rustlog::set_level(l);
+        info_once!("hello");
+    }
+    assert_eq!(lines_from(&buf).len(), 1);
+}
+
+#[test]
+fn local_once_per_call_site() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .build()?;
+
+    for _ in 0..10 {
+        rustlog::local::warn_once!(&lg, "first");
+        rustlog::local::warn_once!(&lg, "second");
+    }
+    let s = mem.text();
+    assert_eq!(s.lines().count(), 2, "{s}");
+    Ok(())
+}
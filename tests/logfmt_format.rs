@@ -0,0 +1,95 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, info_group, DefaultFormat, LogfmtFormat};
+
+/// Splits a logfmt line on spaces outside of quotes and decodes the values.
+fn pairs(line: &str) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let mut chars = line.chars().peekable();
+    while chars.peek().is_some() {
+        let key: String = chars.by_ref().take_while(|&c| c != '=').collect();
+        let mut val = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next().unwrap() {
+                        'n' => val.push('\n'),
+                        't' => val.push('\t'),
+                        c => val.push(c),
+                    },
+                    c => val.push(c),
+                }
+            }
+            assert!(matches!(chars.next(), None | Some(' ')), "junk after quote: {line}");
+        } else {
+            val = chars.by_ref().take_while(|&c| c != ' ').collect();
+        }
+        out.push((key, val));
+    }
+    out
+}
+
+#[test]
+fn logfmt_quotes_values_with_spaces() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(true);
+    rustlog::set_format(Box::new(LogfmtFormat));
+
+    info_group!("net", "retry in {} ms", 200);
+    info!("say \"hi\" a=b\tc");
+    info!("bare");
+
+    let lines = lines_from(&buf);
+    rustlog::set_format(Box::new(DefaultFormat));
+    assert_eq!(lines.len(), 3);
+
+    let first = pairs(&lines[0]);
+    let keys: Vec<&str> = first.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["level", "file", "group", "msg"]);
+    assert_eq!(first[0].1, "info");
+    assert!(first[1].1.starts_with(file!()));
+    assert_eq!(first[2].1, "net");
+    assert_eq!(first[3].1, "retry in 200 ms");
+    assert!(lines[0].ends_with(r#"msg="retry in 200 ms""#), "{}", lines[0]);
+
+    let second = pairs(&lines[1]);
+    assert_eq!(second.last().unwrap().1, "say \"hi\" a=b\tc");
+
+    assert!(lines[2].ends_with(" msg=bare"), "unquoted when not needed: {}", lines[2]);
+}
+
+#[test]
+fn logfmt_omits_disabled_fields() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_show_group(false);
+    rustlog::set_format(Box::new(LogfmtFormat));
+
+    info_group!("net", "");
+
+    let lines = lines_from(&buf);
+    rustlog::set_format(Box::new(DefaultFormat));
+    rustlog::set_show_group(true);
+    assert_eq!(lines, [r#"level=info msg="""#]);
+}
+
+#[test]
+fn logfmt_appends_fields() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_format(Box::new(LogfmtFormat));
+
+    rustlog::info_kv!("done", status = 200, path = "/a b");
+
+    let lines = lines_from(&buf);
+    rustlog::set_format(Box::new(DefaultFormat));
+    assert_eq!(lines, [r#"level=info msg=done status=200 path="/a b""#]);
+}
@@ -0,0 +1,54 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::local::{info as linfo, warn as lwarn, Logger};
+use rustlog::Level;
+
+#[test]
+fn child_inherits_settings_and_shares_the_sink() -> io::Result<()> {
+    let mem = Mem::default();
+    let parent = Logger::builder()
+        .writer(Box::new(mem.clone()))
+        .set_level(Level::Warn)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+
+    let child = parent.child();
+    child.set_level(Level::Info);
+    child.set_default_group(Some("db"));
+
+    linfo!(&parent, "parent info (filtered)");
+    lwarn!(&parent, "parent warn");
+    linfo!(&child, "child info");
+
+    assert_eq!(parent.level(), Level::Warn);
+    assert_eq!(child.level(), Level::Info);
+    assert!(!child.show_time() && !child.show_file_line());
+    let out = String::from_utf8(mem.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(out, "WARN  parent warn\nINFO  [db] child info\n");
+    Ok(())
+}
+
+#[test]
+fn retargeting_the_child_leaves_the_parent_alone() -> io::Result<()> {
+    let (shared, own) = (Mem::default(), Mem::default());
+    let parent = Logger::builder()
+        .writer(Box::new(shared.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+    let child = parent.child();
+    child.set_writer(Box::new(own.clone()));
+
+    linfo!(&parent, "to shared");
+    linfo!(&child, "to own");
+
+    assert_eq!(&*shared.0.lock().unwrap(), b"INFO  to shared\n");
+    assert_eq!(&*own.0.lock().unwrap(), b"INFO  to own\n");
+    Ok(())
+}
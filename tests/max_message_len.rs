@@ -0,0 +1,31 @@
+mod helpers;
+use helpers::*;
+use rustlog::info;
+
+#[test]
+fn long_messages_are_cut_with_a_marker() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    rustlog::set_max_message_len(Some(100));
+    let big = "x".repeat(10 * 1024);
+    info!("{big}");
+    info!("{}", "é".repeat(10)); // 2 bytes each; a 100-byte cap doesn't touch it
+    rustlog::set_max_message_len(Some(5));
+    info!("{}", "é".repeat(10));
+    info!("short");
+    rustlog::set_max_message_len(None);
+    info!("{big}");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 5);
+    let marker = "…(truncated)";
+    assert_eq!(lines[0].len(), "INFO  ".len() + 100 + marker.len());
+    assert!(lines[0].ends_with(&format!("x{marker}")), "{:?}", lines[0]);
+    assert_eq!(lines[1], format!("INFO  {}", "é".repeat(10)));
+    assert_eq!(lines[2], format!("INFO  éé{marker}"));
+    assert_eq!(lines[3], "INFO  short");
+    assert_eq!(lines[4].len(), "INFO  ".len() + big.len());
+}
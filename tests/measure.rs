@@ -0,0 +1,151 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use rustlog::local::Logger;
+use rustlog::{ct_enabled, measure, scope_time, Level};
+
+#[test]
+fn scope_time_block_value_and_level() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_group(true);
+    rustlog::set_show_file_line(false);
+
+    let v = scope_time!(level: Level::Warn, "warned", { 40 + 2 });
+    assert_eq!(v, 42);
+    {
+        scope_time!(level: Level::Debug, "raii");
+    }
+
+    // release builds compile Debug records out
+    let debug = ct_enabled(Level::Debug);
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 1 + usize::from(debug), "{lines:?}");
+    assert!(lines[0].starts_with("WARN  [warned] took "), "{}", lines[0]);
+    if debug {
+        assert!(lines[1].starts_with("DEBUG [raii] took "), "{}", lines[1]);
+    }
+
+    rustlog::set_level(Level::Info);
+    scope_time!(level: Level::Debug, "filtered", {});
+    assert_eq!(
+        lines_from(&buf).len(),
+        lines.len(),
+        "below runtime level must not log"
+    );
+}
+
+#[test]
+fn measure_returns_value_and_duration() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_group(true);
+    rustlog::set_show_file_line(false);
+
+    let (v, took) = measure!("work", {
+        std::thread::sleep(Duration::from_millis(5));
+        "done"
+    });
+    assert_eq!(v, "done");
+    assert!(took >= Duration::from_millis(5));
+
+    let ((), _) = measure!(level: Level::Trace, "quiet", {});
+
+    // release builds compile Trace records out
+    let trace = ct_enabled(Level::Trace);
+    let lines = lines_from(&buf);
+    assert_eq!(
+        lines.len(),
+        1 + usize::from(trace),
+        "measure logs exactly once: {lines:?}"
+    );
+    assert!(lines[0].starts_with("INFO  [work] took "), "{}", lines[0]);
+    if trace {
+        assert!(lines[1].starts_with("TRACE [quiet] took "), "{}", lines[1]);
+    }
+}
+
+#[test]
+fn local_measure_and_level() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .set_level(Level::Trace)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+
+    let ((), took) = rustlog::local::measure!(&lg, level: Level::Error, "local", {
+        std::thread::sleep(Duration::from_millis(1));
+    });
+    assert!(took >= Duration::from_millis(1));
+    let n = rustlog::local::scope_time!(&lg, level: Level::Debug, "block", { 7 });
+    assert_eq!(n, 7);
+
+    // release builds compile Debug records out
+    let debug = ct_enabled(Level::Debug);
+    let s = mem.text();
+    let lines: Vec<_> = s.lines().collect();
+    assert_eq!(lines.len(), 1 + usize::from(debug), "{s}");
+    assert!(lines[0].starts_with("ERROR [local] took "), "{s}");
+    if debug {
+        assert!(lines[1].starts_with("DEBUG [block] took "), "{s}");
+    }
+    Ok(())
+}
+
+#[test]
+fn scope_time_min_threshold() {
+    // only Info records are expected here, so a failure elsewhere in this binary
+    // must not take this test down with it through the poisoned lock
+    let _g = test_lock().lock().unwrap_or_else(PoisonError::into_inner);
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_group(true);
+    rustlog::set_show_file_line(false);
+
+    let v = scope_time!("fast", min: Duration::from_millis(10), { 1 });
+    assert_eq!(v, 1);
+    {
+        scope_time!("fast-raii", min: Duration::from_millis(10));
+    }
+    assert!(
+        lines_from(&buf).is_empty(),
+        "scopes under the threshold must not log"
+    );
+
+    scope_time!("slow", min: Duration::from_millis(10), {
+        std::thread::sleep(Duration::from_millis(15));
+    });
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 1, "{lines:?}");
+    assert!(lines[0].starts_with("INFO  [slow] took "), "{}", lines[0]);
+}
+
+#[test]
+fn local_scope_time_min_threshold() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+
+    rustlog::local::scope_time!(&lg, "fast", min: Duration::from_millis(10), {});
+    assert!(mem.text().is_empty());
+    {
+        rustlog::local::scope_time!(&lg, "slow", min: Duration::from_millis(10));
+        std::thread::sleep(Duration::from_millis(15));
+    }
+    let s = mem.text();
+    assert!(s.starts_with("INFO  [slow] took "), "{s}");
+    Ok(())
+}
@@ -0,0 +1,50 @@
+mod helpers;
+use helpers::*;
+use rustlog::info;
+use std::io;
+
+mod inner {
+    pub fn log() {
+        rustlog::info!("from inner");
+    }
+}
+
+#[test]
+fn module_path_shown_only_when_enabled() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    info!("hidden");
+    rustlog::set_show_module(true);
+    assert!(rustlog::show_module());
+    info!("shown");
+    inner::log();
+    rustlog::set_show_module(false);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 3);
+    assert!(!lines[0].contains('{'), "{:?}", lines[0]);
+    assert!(
+        lines[1].contains(&format!("{{{}}}", module_path!())),
+        "{:?}",
+        lines[1]
+    );
+    assert!(lines[2].contains("{module_path::inner}"), "{:?}", lines[2]);
+}
+
+#[test]
+fn local_logger_shows_module() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = rustlog::local::Logger::builder()
+        .set_writer(Box::new(mem.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_show_module(true)
+        .build()?;
+    rustlog::local::info!(&lg, "local");
+    assert_eq!(mem.lines(), ["INFO  {module_path} local"]);
+    Ok(())
+}
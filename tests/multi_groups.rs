@@ -0,0 +1,49 @@
+mod helpers;
+use helpers::*;
+use rustlog::local::{info_groups as linfo_groups, Logger};
+use rustlog::{info_groups, warn_groups, Level};
+
+#[test]
+fn all_tags_render_in_order_and_hide_together() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_level(Level::Warn);
+    rustlog::set_group_level("net", Level::Info);
+
+    warn_groups!(&["net", "retry"], "attempt {}", 2);
+    // the first group picks the level: "net" allows info, "db" doesn't
+    info_groups!(&["net", "db"], "net info");
+    info_groups!(&["db", "net"], "db info");
+    warn_groups!(&[], "no groups");
+    rustlog::set_show_group(false);
+    warn_groups!(&["net", "retry"], "hidden");
+    rustlog::set_show_group(true);
+    rustlog::clear_group_levels();
+
+    assert_eq!(
+        lines_from(&buf),
+        [
+            "WARN  [net][retry] attempt 2",
+            "INFO  [net][db] net info",
+            "WARN  no groups",
+            "WARN  hidden",
+        ]
+    );
+}
+
+#[test]
+fn local_logger_renders_every_tag() {
+    let cap = rustlog::sink::CaptureSink::new(8);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_group_delimiters("<", ">")
+        .build()
+        .unwrap();
+    linfo_groups!(&lg, &["a", "b", "c"], "three");
+    assert_eq!(cap.lines(), ["INFO  <a><b><c> three"]);
+}
@@ -0,0 +1,60 @@
+mod helpers;
+use helpers::*;
+
+use std::io::{self, Write};
+
+use rustlog::local::info as linfo;
+use rustlog::local::Logger;
+use rustlog::sink::{tee, MultiWriter};
+
+struct Broken;
+impl Write for Broken {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "broken"))
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Other, "broken"))
+    }
+}
+
+#[test]
+fn global_info_lands_in_both_buffers() {
+    let (a, b) = (Mem::default(), Mem::default());
+    rustlog::set_writer(Box::new(tee(Box::new(a.clone()), Box::new(b.clone()))));
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+
+    rustlog::info!("fan out");
+
+    assert!(a.text().contains("fan out"), "a: {}", a.text());
+    assert_eq!(a.text(), b.text());
+}
+
+#[test]
+fn local_logger_accepts_multi_writer() -> io::Result<()> {
+    let (a, b) = (Mem::default(), Mem::default());
+    let multi = MultiWriter::new(vec![Box::new(a.clone()), Box::new(b.clone())]);
+    let lg = Logger::builder()
+        .set_writer(Box::new(multi))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .build()?;
+
+    linfo!(&lg, "local fan out");
+
+    assert!(a.text().contains("local fan out"));
+    assert_eq!(a.text(), b.text());
+    Ok(())
+}
+
+#[test]
+fn failing_writer_does_not_starve_the_rest() {
+    let ok = Mem::default();
+    let mut multi = MultiWriter::new(vec![Box::new(Broken)]);
+    multi.push(Box::new(ok.clone()));
+    assert_eq!(multi.len(), 2);
+
+    assert!(multi.write_all(b"hello\n").is_err());
+    assert!(multi.flush().is_err());
+    assert_eq!(ok.text(), "hello\n");
+}
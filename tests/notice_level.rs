@@ -0,0 +1,82 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, notice, notice_group, warn, Level};
+
+#[test]
+fn notice_sits_between_info_and_warn() {
+    assert!(Level::Info < Level::Notice && Level::Notice < Level::Warn);
+    assert_eq!("NOTICE".parse::<Level>(), Ok(Level::Notice));
+    // the text-line labels parse back
+    assert_eq!("NOTE".parse::<Level>(), Ok(Level::Notice));
+    assert_eq!("note".parse::<Level>(), Ok(Level::Notice));
+    assert_eq!(Level::Notice.to_string(), "notice");
+    assert_eq!(Level::try_from(Level::Notice as u8), Ok(Level::Notice));
+    assert_eq!(Level::try_from(Level::Fatal as u8), Ok(Level::Fatal));
+}
+
+#[test]
+fn env_level_notice() {
+    let _g = test_lock().lock().unwrap();
+    std::env::set_var("RUST_LOG_LEVEL", "notice");
+    rustlog::init_from_env();
+    std::env::remove_var("RUST_LOG_LEVEL");
+
+    assert_eq!(rustlog::level(), Level::Notice);
+    rustlog::set_level(Level::Trace);
+}
+
+#[test]
+fn notice_macros_and_threshold() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_level(Level::Notice);
+
+    info!("dropped");
+    notice!("deployed v{}", 2);
+    notice_group!("ops", "drained");
+    warn!("kept");
+
+    rustlog::set_level(Level::Trace);
+    assert_eq!(
+        lines_from(&buf),
+        ["NOTE  deployed v2", "NOTE  [ops] drained", "WARN  kept"]
+    );
+}
+
+#[test]
+fn local_notice_macros() -> std::io::Result<()> {
+    use rustlog::local::{notice as lnotice, notice_group as lnotice_group, Logger};
+
+    let cap = rustlog::sink::CaptureSink::new(8);
+    let lg = Logger::builder()
+        .writer(Box::new(cap.clone()))
+        .set_level(Level::Notice)
+        .set_show_file_line(false)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .build()?;
+
+    lnotice!(&lg, "up");
+    lnotice_group!(&lg, "ops", "up");
+
+    assert_eq!(cap.lines(), ["NOTE  up", "NOTE  [ops] up"]);
+    Ok(())
+}
+
+#[cfg(feature = "color")]
+#[test]
+fn notice_has_its_own_color() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_color_mode(rustlog::ColorMode::Always);
+
+    notice!("n");
+
+    rustlog::set_color_mode(rustlog::ColorMode::Never);
+    let line = lines_from(&buf).remove(0);
+    assert!(line.starts_with("\x1b[34mNOTE \x1b[0m"), "{line:?}");
+}
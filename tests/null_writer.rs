@@ -0,0 +1,37 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rustlog::local::{debug as ldebug, info as linfo, Logger};
+use rustlog::Level;
+
+static FORMATTED: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted;
+impl fmt::Display for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FORMATTED.fetch_add(1, Ordering::Relaxed);
+        f.write_str("counted")
+    }
+}
+
+#[test]
+fn null_writer_formats_enabled_records_only() -> std::io::Result<()> {
+    let lg = Logger::builder().null().set_level(Level::Info).build()?;
+
+    ldebug!(&lg, "{}", Counted);
+    assert_eq!(
+        FORMATTED.load(Ordering::Relaxed),
+        0,
+        "filtered record was formatted"
+    );
+    linfo!(&lg, "{}", Counted);
+    assert_eq!(FORMATTED.load(Ordering::Relaxed), 1);
+    lg.flush()?;
+
+    rustlog::set_null();
+    rustlog::set_level(Level::Info);
+    rustlog::debug!("{}", Counted);
+    rustlog::info!("{}", Counted);
+    assert_eq!(FORMATTED.load(Ordering::Relaxed), 2);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+mod helpers;
+use helpers::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rustlog::{debug, info, warn, warn_group, Level};
+
+#[test]
+fn observer_counts_emitted_warnings() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_level(Level::Info);
+
+    let warns = Arc::new(AtomicUsize::new(0));
+    let groups = Arc::new(Mutex::new(Vec::new()));
+    let (w, g) = (warns.clone(), groups.clone());
+    rustlog::set_observer(Box::new(move |l, group| {
+        if l == Level::Warn {
+            w.fetch_add(1, Ordering::SeqCst);
+        }
+        g.lock().unwrap().push(group.map(str::to_owned));
+    }));
+    rustlog::set_filter(Box::new(|r| r.group != Some("muted")));
+
+    warn!("one");
+    info!("not a warning");
+    debug!("below the level, never observed");
+    warn_group!("db", "two");
+    warn_group!("muted", "filtered, never observed");
+    warn!("three");
+
+    rustlog::clear_filter();
+    rustlog::clear_observer();
+    warn!("after clear");
+
+    assert_eq!(warns.load(Ordering::SeqCst), 3);
+    assert_eq!(
+        *groups.lock().unwrap(),
+        [None, None, Some("db".to_owned()), None]
+    );
+    // observing doesn't change what's written
+    assert_eq!(lines_from(&buf).len(), 5);
+}
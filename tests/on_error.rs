@@ -0,0 +1,62 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rustlog::local::{info as linfo, Logger};
+use rustlog::{Level, Target};
+
+struct DiskFull;
+impl Write for DiskFull {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn callback_fires_on_write_failure() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let s = seen.clone();
+    rustlog::set_writer(Box::new(DiskFull));
+    rustlog::set_target(Target::Writer);
+    rustlog::set_level(Level::Info);
+    rustlog::set_on_error(Box::new(move |e| s.lock().unwrap().push(e.to_string())));
+
+    rustlog::info!("lost");
+    rustlog::debug!("filtered, never written");
+    let err = rustlog::try_emit(Level::Warn, None, file!(), line!(), format_args!("x"));
+
+    rustlog::clear_on_error();
+    rustlog::info!("lost quietly");
+
+    assert_eq!(err.unwrap_err().to_string(), "disk full");
+    // `try_emit` returns the error instead of calling back
+    assert_eq!(*seen.lock().unwrap(), ["disk full"]);
+}
+
+#[test]
+fn local_try_emit_and_reentrant_callback() -> io::Result<()> {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let lg: &'static Logger = Box::leak(Box::new(
+        Logger::builder()
+            .writer(Box::new(DiskFull))
+            .build()?,
+    ));
+    lg.set_on_error(Box::new(move |_| {
+        CALLS.fetch_add(1, Ordering::Relaxed);
+        // fails again, but must not call back into here
+        linfo!(lg, "reporting");
+    }));
+
+    linfo!(lg, "lost");
+    assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+    let err = lg.try_emit_to(Level::Info, None, file!(), line!(), format_args!("x"));
+    assert_eq!(err.unwrap_err().kind(), io::ErrorKind::Other);
+    assert!(lg
+        .try_emit_to(Level::Debug, None, file!(), line!(), format_args!("x"))
+        .is_ok());
+    Ok(())
+}
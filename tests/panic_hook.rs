@@ -0,0 +1,25 @@
+mod helpers;
+use helpers::*;
+
+#[test]
+fn panic_is_logged_at_fatal() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_group(true);
+    rustlog::set_show_file_line(true);
+
+    rustlog::install_panic_hook();
+    let line = line!() + 1;
+    let r = std::panic::catch_unwind(|| panic!("boom {}", 42));
+    assert!(r.is_err());
+    let _ = std::panic::take_hook();
+
+    let lines = lines_from(&buf);
+    let hit = lines
+        .iter()
+        .find(|l| l.contains("FATAL") && l.contains("[panic]"))
+        .unwrap_or_else(|| panic!("no FATAL [panic] line in {lines:?}"));
+    assert!(hit.ends_with("boom 42"), "{hit}");
+    assert!(hit.contains(&format!("<{}:{line}>", file!())), "{hit}");
+}
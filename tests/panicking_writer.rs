@@ -0,0 +1,63 @@
+use std::io::{self, Write};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+use rustlog::local::{info as linfo, Logger};
+
+/// Panics on its first write, then behaves
+#[derive(Clone, Default)]
+struct FailsOnce {
+    out: Arc<Mutex<Vec<u8>>>,
+    failed: bool,
+}
+impl Write for FailsOnce {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        if !self.failed {
+            self.failed = true;
+            panic!("first write fails");
+        }
+        self.out.lock().unwrap().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn logging_recovers_after_a_writer_panic() {
+    let w = FailsOnce::default();
+    rustlog::set_writer(Box::new(w.clone()));
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+
+    // the panic reaches the caller of the failing record...
+    assert!(catch_unwind(|| rustlog::info!("lost")).is_err());
+    // ...but doesn't poison the sink for later ones
+    rustlog::info!("first");
+    rustlog::warn!("second");
+    rustlog::flush().unwrap();
+    rustlog::reset_target();
+
+    let out = String::from_utf8(w.out.lock().unwrap().clone()).unwrap();
+    assert_eq!(out, "INFO  first\nWARN  second\n");
+}
+
+#[test]
+fn local_logger_recovers_too() {
+    let w = FailsOnce::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(w.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()
+        .unwrap();
+
+    assert!(catch_unwind(AssertUnwindSafe(|| linfo!(&lg, "lost"))).is_err());
+    linfo!(&lg, "after");
+
+    let out = String::from_utf8(w.out.lock().unwrap().clone()).unwrap();
+    assert_eq!(out, "INFO  after\n");
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "parking_lot")]
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use rustlog::local::{info as linfo, Logger};
+
+const N_THREADS: usize = 4;
+const N_LINES: usize = 50;
+
+/// Panics on lines containing "boom", after writing part of them
+#[derive(Clone, Default)]
+struct Fragile(Arc<Mutex<Vec<u8>>>);
+impl Write for Fragile {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        if let Some(i) = bytes.windows(4).position(|w| w == b"boom") {
+            self.0.lock().unwrap().extend_from_slice(&bytes[..i]);
+            self.0.lock().unwrap().push(b'\n');
+            panic!("writer failed mid-write");
+        }
+        self.0.lock().unwrap().extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn panicking_writer_does_not_poison_the_logger() {
+    let w = Fragile::default();
+    let lg = Logger::builder()
+        .set_writer(Box::new(w.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()
+        .unwrap();
+
+    std::thread::scope(|s| {
+        let lg = &lg;
+        let bad = s.spawn(move || linfo!(lg, "boom"));
+        for t in 0..N_THREADS {
+            s.spawn(move || {
+                for i in 0..N_LINES {
+                    linfo!(lg, "T{t} #{i}");
+                }
+            });
+        }
+        assert!(bad.join().is_err());
+    });
+    // the panic happened while the sink locks were held; later lines still work
+    linfo!(&lg, "after");
+    lg.flush().unwrap();
+
+    let text = String::from_utf8(w.0.lock().unwrap().clone()).unwrap();
+    let whole = text.lines().filter(|l| l.contains(" T")).count();
+    assert_eq!(whole, N_THREADS * N_LINES);
+    assert_eq!(text.lines().last(), Some("INFO  after"));
+}
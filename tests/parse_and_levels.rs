@@ -30,17 +30,158 @@ fn level_set_and_get_all_variants() {
 }
 
 #[test]
-fn compile_time_floor_matches_profile() {
-    // debug builds include Trace; release builds strip Trace/Debug at compile time
-    #[cfg(debug_assertions)]
-    {
-        assert!(rustlog::ct_enabled(Level::Trace));
-        assert!(rustlog::ct_enabled(Level::Debug));
+#[cfg(all(
+    debug_assertions,
+    not(any(
+        feature = "max_level_off",
+        feature = "max_level_error",
+        feature = "max_level_warn",
+        feature = "max_level_info",
+        feature = "max_level_debug",
+    ))
+))]
+fn debug_builds_keep_every_level() {
+    for l in Level::all() {
+        assert!(rustlog::ct_enabled(l), "{l}");
     }
-    #[cfg(not(debug_assertions))]
-    {
-        assert!(!rustlog::ct_enabled(Level::Trace));
-        assert!(!rustlog::ct_enabled(Level::Debug));
-        assert!(rustlog::ct_enabled(Level::Info));
+}
+
+#[test]
+#[cfg(all(
+    not(debug_assertions),
+    not(any(
+        feature = "max_level_off",
+        feature = "max_level_error",
+        feature = "max_level_warn",
+        feature = "max_level_info",
+        feature = "max_level_debug",
+        feature = "max_level_trace",
+        feature = "release_max_level_off",
+        feature = "release_max_level_error",
+        feature = "release_max_level_warn",
+        feature = "release_max_level_info",
+        feature = "release_max_level_debug",
+        feature = "release_max_level_trace",
+    ))
+))]
+fn release_builds_strip_trace_and_debug() {
+    assert!(!rustlog::ct_enabled(Level::Trace));
+    assert!(!rustlog::ct_enabled(Level::Debug));
+    assert!(rustlog::ct_enabled(Level::Info));
+}
+
+#[test]
+#[cfg(all(
+    feature = "max_level_info",
+    not(any(
+        feature = "max_level_off",
+        feature = "max_level_error",
+        feature = "max_level_warn",
+    )),
+    any(
+        debug_assertions,
+        not(any(
+            feature = "release_max_level_off",
+            feature = "release_max_level_error",
+            feature = "release_max_level_warn",
+            feature = "release_max_level_info",
+            feature = "release_max_level_debug",
+            feature = "release_max_level_trace",
+        ))
+    )
+))]
+fn max_level_info_strips_debug() {
+    assert!(!rustlog::ct_enabled(Level::Debug));
+    assert!(rustlog::ct_enabled(Level::Info));
+}
+
+#[test]
+#[cfg(all(
+    feature = "max_level_off",
+    any(
+        debug_assertions,
+        not(any(
+            feature = "release_max_level_off",
+            feature = "release_max_level_error",
+            feature = "release_max_level_warn",
+            feature = "release_max_level_info",
+            feature = "release_max_level_debug",
+            feature = "release_max_level_trace",
+        ))
+    )
+))]
+fn max_level_off_strips_everything() {
+    for l in Level::all() {
+        assert!(!rustlog::ct_enabled(l), "{l}");
+    }
+}
+
+#[test]
+#[cfg(all(feature = "release_max_level_warn", not(debug_assertions)))]
+fn release_max_level_warn_strips_info_in_release() {
+    assert!(!rustlog::ct_enabled(Level::Info));
+    assert!(!rustlog::ct_enabled(Level::Notice));
+    assert!(rustlog::ct_enabled(Level::Warn));
+}
+
+#[test]
+#[cfg(all(
+    feature = "release_max_level_warn",
+    debug_assertions,
+    not(any(
+        feature = "max_level_off",
+        feature = "max_level_error",
+        feature = "max_level_warn",
+        feature = "max_level_info",
+        feature = "max_level_debug",
+    ))
+))]
+fn release_max_level_warn_keeps_debug_builds_verbose() {
+    assert!(rustlog::ct_enabled(Level::Trace));
+}
+
+#[test]
+fn level_from_str_and_display_round_trip() {
+    assert_eq!(Level::from_str("trace").ok(), Some(Level::Trace));
+    assert_eq!(Level::from_str("DEBUG").ok(), Some(Level::Debug));
+    assert_eq!(Level::from_str("Info").ok(), Some(Level::Info));
+    assert_eq!(Level::from_str("warn").ok(), Some(Level::Warn));
+    assert_eq!(Level::from_str("error").ok(), Some(Level::Error));
+    assert_eq!(Level::from_str("FATAL").ok(), Some(Level::Fatal));
+    assert!(Level::from_str("").is_err());
+    assert!(Level::from_str("verbose").is_err());
+
+    for &lvl in &[
+        Level::Trace,
+        Level::Debug,
+        Level::Info,
+        Level::Notice,
+        Level::Warn,
+        Level::Error,
+        Level::Fatal,
+    ] {
+        assert_eq!(lvl.to_string().parse::<Level>().ok(), Some(lvl));
+    }
+    assert_eq!(Level::Warn.to_string(), "warn");
+}
+
+#[test]
+fn level_as_str_and_all() {
+    let all = Level::all();
+    assert_eq!(
+        all.map(Level::as_str),
+        ["TRACE", "DEBUG", "INFO", "NOTICE", "WARN", "ERROR", "FATAL"]
+    );
+    assert!(all.windows(2).all(|w| w[0] < w[1]), "ascending order");
+}
+
+#[test]
+fn level_try_from_u8_is_checked() {
+    for (i, lvl) in Level::all().into_iter().enumerate() {
+        let x = u8::try_from(i).unwrap();
+        assert_eq!(Level::try_from(x), Ok(lvl));
+        assert_eq!(Level::try_from(lvl as u8), Ok(lvl));
     }
+    assert_eq!(Level::try_from(7u8), Err(rustlog::ParseLevelError));
+    assert!(Level::try_from(u8::MAX).is_err());
 }
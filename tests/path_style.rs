@@ -0,0 +1,38 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, PathStyle};
+
+#[test]
+fn file_name_style_drops_directories() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(true);
+
+    rustlog::set_path_style(PathStyle::FileName);
+    info!("short");
+    rustlog::set_path_style(PathStyle::Relative("tests"));
+    info!("relative");
+    rustlog::set_path_style(PathStyle::Full);
+    info!("full");
+
+    let lines = lines_from(&buf);
+    let path = |l: &str| l.split('<').nth(1).unwrap().split(':').next().unwrap().to_owned();
+    assert_eq!(path(&lines[0]), "path_style.rs");
+    assert!(!lines[0].contains('/'), "{}", lines[0]);
+    assert_eq!(path(&lines[1]), "path_style.rs");
+    assert_eq!(path(&lines[2]), file!());
+}
+
+#[test]
+fn path_style_apply() {
+    let dep = "/home/me/.cargo/registry/src/foo-1.0/src/lib.rs";
+    assert_eq!(PathStyle::Full.apply(dep), dep);
+    assert_eq!(PathStyle::FileName.apply(dep), "lib.rs");
+    assert_eq!(PathStyle::FileName.apply(r"C:\src\main.rs"), "main.rs");
+    assert_eq!(
+        PathStyle::Relative("/home/me/.cargo/registry/src").apply(dep),
+        "foo-1.0/src/lib.rs"
+    );
+    assert_eq!(PathStyle::Relative("/elsewhere").apply(dep), dep);
+}
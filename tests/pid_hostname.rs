@@ -0,0 +1,51 @@
+mod helpers;
+use helpers::*;
+use rustlog::info;
+
+#[test]
+fn pid_token_is_the_process_id() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    info!("no pid");
+    rustlog::set_show_pid(true);
+    assert!(rustlog::show_pid());
+    info!("with pid");
+    rustlog::set_show_pid(false);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 2);
+    assert!(!lines[0].contains("[pid:"), "{:?}", lines[0]);
+    let pid = lines[1]
+        .split("[pid:")
+        .nth(1)
+        .and_then(|rest| rest.split(']').next())
+        .expect("pid token");
+    assert_eq!(pid.parse::<u32>().unwrap(), std::process::id());
+}
+
+#[test]
+fn hostname_is_shown_when_known() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    rustlog::set_show_hostname(true);
+    info!("with host");
+    rustlog::set_show_hostname(false);
+    info!("no host");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 2);
+    if let Ok(host) = std::env::var("HOSTNAME") {
+        assert!(
+            lines[0].contains(&format!("[host:{}]", host.trim())),
+            "{:?}",
+            lines[0]
+        );
+    }
+    assert!(!lines[1].contains("[host:"), "{:?}", lines[1]);
+}
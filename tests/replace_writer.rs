@@ -0,0 +1,44 @@
+mod helpers;
+use helpers::*;
+
+use std::io;
+
+use rustlog::local::{info as linfo, Logger};
+use rustlog::Target;
+
+#[test]
+fn redirect_and_restore_the_global_writer() {
+    let (original, capture) = (Mem::default(), Mem::default());
+    rustlog::set_writer(Box::new(original.clone()));
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+
+    rustlog::info!("before");
+    let prev = rustlog::replace_writer(Box::new(capture.clone())).unwrap();
+    rustlog::info!("during");
+    let restored = rustlog::replace_writer(prev);
+    rustlog::info!("after");
+
+    assert!(restored.is_some());
+    assert_eq!(original.text(), "INFO  before\nINFO  after\n");
+    assert_eq!(capture.text(), "INFO  during\n");
+}
+
+#[test]
+fn replacing_without_a_writer_returns_none() -> io::Result<()> {
+    let mem = Mem::default();
+    let lg = Logger::builder()
+        .stderr()
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+
+    assert!(lg.replace_writer(Box::new(mem.clone())).is_none());
+    assert_eq!(lg.target(), Target::Writer);
+    linfo!(&lg, "captured");
+
+    assert_eq!(mem.text(), "INFO  captured\n");
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use std::fs;
+use std::path::PathBuf;
+
+use rustlog::local::{info as linfo, Logger};
+
+fn temp_dir(tag: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!(
+        "rustlog_rotate_{tag}_{}_{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    fs::create_dir_all(&p).unwrap();
+    p
+}
+
+fn read_lines(p: &PathBuf) -> Vec<String> {
+    fs::read_to_string(p)
+        .unwrap_or_default()
+        .lines()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn global_rotation_splits_on_line_boundaries() {
+    let dir = temp_dir("global");
+    let base = dir.join("app.log");
+    // each line is "INFO  line NN\n" = 14 bytes; 3 lines fit in 45 bytes
+    rustlog::set_rotating_file(&base, 45, 2).expect("open rotating file");
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+
+    for i in 0..10 {
+        rustlog::info!("line {i:02}");
+    }
+
+    let current = read_lines(&base);
+    let one = read_lines(&dir.join("app.log.1"));
+    let two = read_lines(&dir.join("app.log.2"));
+    assert!(!dir.join("app.log.3").exists(), "only `keep` backups are retained");
+    assert_eq!(current, ["INFO  line 09"]);
+    assert_eq!(one, ["INFO  line 06", "INFO  line 07", "INFO  line 08"]);
+    assert_eq!(two, ["INFO  line 03", "INFO  line 04", "INFO  line 05"]);
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn builder_rotation() -> std::io::Result<()> {
+    let dir = temp_dir("local");
+    let base = dir.join("app.log");
+    let lg = Logger::builder()
+        .rotating_file(&base, 30, 1)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()?;
+
+    for i in 0..3 {
+        linfo!(&lg, "line {i:02}");
+    }
+
+    assert_eq!(read_lines(&base), ["INFO  line 02"]);
+    assert_eq!(read_lines(&dir.join("app.log.1")), ["INFO  line 00", "INFO  line 01"]);
+    assert!(!dir.join("app.log.2").exists());
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+fn direct_write_never_splits_a_long_line() -> std::io::Result<()> {
+    let dir = temp_dir("direct");
+    let base = dir.join("app.log");
+    let lg = Logger::builder()
+        .rotating_file(&base, 3000, 2)
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_direct_write(true)
+        .build()?;
+
+    // longer than a `LineWriter` buffer, and two don't fit in one file
+    let (a, b) = ("a".repeat(2000), "b".repeat(2000));
+    linfo!(&lg, "{a}");
+    linfo!(&lg, "{b}");
+
+    assert_eq!(read_lines(&dir.join("app.log.1")), [format!("INFO  {a}")]);
+    assert_eq!(read_lines(&base), [format!("INFO  {b}")]);
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -0,0 +1,19 @@
+mod helpers;
+use helpers::*;
+use rustlog::info;
+
+#[test]
+fn control_chars_are_escaped_when_enabled() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    rustlog::set_sanitize(true);
+    info!("a\nb\x1b[31m\tc\u{7f}");
+    rustlog::set_sanitize(false);
+    info!("x\ny");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines, ["INFO  a\\nb\\x1b[31m\\tc\\x7f", "INFO  x", "y"]);
+}
@@ -0,0 +1,63 @@
+mod helpers;
+use helpers::*;
+use rustlog::{info, info_group, scope_group, warn, Level};
+
+#[test]
+fn scope_tags_lines_inside_only() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    let v = scope_group!("db", {
+        info!("connecting");
+        warn!("slow");
+        7
+    });
+    info!("after");
+
+    assert_eq!(v, 7);
+    assert_eq!(
+        lines_from(&buf),
+        ["INFO  [db] connecting", "WARN  [db] slow", "INFO  after"]
+    );
+}
+
+#[test]
+fn nested_scopes_innermost_wins_and_explicit_group_beats_scope() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    {
+        scope_group!("db");
+        scope_group!("db.pool", {
+            info!("inner");
+            info_group!("net", "explicit");
+        });
+        info!("outer");
+    }
+
+    assert_eq!(
+        lines_from(&buf),
+        ["INFO  [db.pool] inner", "INFO  [net] explicit", "INFO  [db] outer"]
+    );
+}
+
+#[test]
+fn scope_group_uses_group_levels() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_group_level("quiet", Level::Warn);
+
+    scope_group!("quiet", {
+        info!("dropped");
+        warn!("kept");
+    });
+    rustlog::clear_group_levels();
+
+    assert_eq!(lines_from(&buf), ["WARN  [quiet] kept"]);
+}
@@ -0,0 +1,61 @@
+mod helpers;
+use helpers::*;
+use rustlog::info;
+use rustlog::local::{info as linfo, Logger};
+
+fn seq_of(line: &str) -> u64 {
+    let rest = line.strip_prefix('#').expect("seq token");
+    rest.split_once(' ').unwrap().0.parse().unwrap()
+}
+
+#[test]
+fn lines_are_numbered_without_gaps() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    rustlog::set_show_seq(true);
+    assert!(rustlog::show_seq());
+    for i in 0..5 {
+        info!("line {i}");
+    }
+    rustlog::set_show_seq(false);
+    info!("plain");
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].ends_with("INFO  line 0"), "{:?}", lines[0]);
+    assert_eq!(lines[0].len(), "#000001 INFO  line 0".len(), "{:?}", lines[0]);
+    let first = seq_of(&lines[0]);
+    for (i, line) in lines[..5].iter().enumerate() {
+        assert_eq!(seq_of(line), first + i as u64, "{line:?}");
+    }
+    assert_eq!(lines[5], "INFO  plain");
+}
+
+#[test]
+fn direct_writes_keep_numbers_in_write_order() {
+    let cap = rustlog::sink::CaptureSink::new(1024);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_direct_write(true)
+        .set_show_seq(true)
+        .build()
+        .unwrap();
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let lg = &lg;
+            s.spawn(move || {
+                for i in 0..25 {
+                    linfo!(lg, "t{t} {i}");
+                }
+            });
+        }
+    });
+    let seqs: Vec<u64> = cap.lines().iter().map(|l| seq_of(l)).collect();
+    assert_eq!(seqs, (1..=100).collect::<Vec<_>>());
+}
@@ -0,0 +1,75 @@
+mod helpers;
+use helpers::*;
+
+use rustlog::local::{info as linfo, info_group as linfo_group, Logger};
+use rustlog::sink::CaptureSink;
+use rustlog::{info, info_group, SinkId};
+
+const AUDIT: SinkId = SinkId("audit");
+const NET: SinkId = SinkId("net");
+
+fn by_group(r: &rustlog::Record) -> SinkId {
+    match r.group {
+        Some("audit") => AUDIT,
+        Some("net") => NET,
+        _ => SinkId::DEFAULT,
+    }
+}
+
+#[test]
+fn router_sends_each_group_to_its_sink() {
+    let (main, audit, net) = (
+        CaptureSink::new(8),
+        CaptureSink::new(8),
+        CaptureSink::new(8),
+    );
+    let lg = Logger::builder()
+        .set_writer(Box::new(main.clone()))
+        .set_show_time(false)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .build()
+        .unwrap();
+    lg.register_sink(AUDIT, Box::new(audit.clone()));
+    lg.register_sink(NET, Box::new(net.clone()));
+    lg.set_sink_router(Box::new(by_group));
+
+    linfo_group!(&lg, "audit", "login ann");
+    linfo_group!(&lg, "net", "connected");
+    linfo_group!(&lg, "db", "query");
+    linfo!(&lg, "plain");
+    // an id with no sink falls back to the regular target
+    assert!(lg.unregister_sink(NET));
+    assert!(!lg.unregister_sink(NET));
+    linfo_group!(&lg, "net", "retry");
+
+    assert_eq!(audit.lines(), ["INFO  [audit] login ann"]);
+    assert_eq!(net.lines(), ["INFO  [net] connected"]);
+    assert_eq!(
+        main.lines(),
+        ["INFO  [db] query", "INFO  plain", "INFO  [net] retry"]
+    );
+}
+
+#[test]
+fn global_without_router_is_unchanged() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    let audit = CaptureSink::new(8);
+    rustlog::register_sink(AUDIT, Box::new(audit.clone()));
+
+    info_group!("audit", "no router yet");
+    rustlog::set_sink_router(Box::new(by_group));
+    info_group!("audit", "routed");
+    info!("default");
+    rustlog::clear_sink_router();
+    assert!(rustlog::unregister_sink(AUDIT));
+
+    assert_eq!(audit.lines(), ["INFO  [audit] routed"]);
+    assert_eq!(
+        lines_from(&buf),
+        ["INFO  [audit] no router yet", "INFO  default"]
+    );
+}
@@ -0,0 +1,48 @@
+mod helpers;
+use helpers::*;
+use rustlog::stopwatch;
+use std::time::Duration;
+
+/// Parses the `HumanDuration` forms below a minute back into a `Duration`
+fn parse_human(s: &str) -> Duration {
+    let (num, unit) = s.split_once(' ').unwrap();
+    let n: f64 = num.parse().unwrap();
+    match unit {
+        "ns" => Duration::from_secs_f64(n / 1e9),
+        "us" => Duration::from_secs_f64(n / 1e6),
+        "ms" => Duration::from_secs_f64(n / 1e3),
+        "s" => Duration::from_secs_f64(n),
+        u => panic!("unexpected unit {u}"),
+    }
+}
+
+fn total_of(line: &str) -> Duration {
+    let start = line.find("total ").unwrap() + "total ".len();
+    parse_human(line[start..].trim_end_matches(')'))
+}
+
+#[test]
+fn laps_and_total_are_logged_with_increasing_totals() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+
+    {
+        let sw = stopwatch!("deploy");
+        std::thread::sleep(Duration::from_millis(3));
+        let lap1 = sw.lap("build");
+        std::thread::sleep(Duration::from_millis(3));
+        let lap2 = sw.lap("upload");
+        assert!(lap1 >= Duration::from_millis(3) && lap2 >= Duration::from_millis(3));
+    }
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 3, "{lines:?}");
+    assert!(lines[0].starts_with("INFO  [deploy] build took "), "{lines:?}");
+    assert!(lines[1].starts_with("INFO  [deploy] upload took "), "{lines:?}");
+    assert!(lines[2].starts_with("INFO  [deploy] total "), "{lines:?}");
+
+    let totals: Vec<_> = lines.iter().map(|l| total_of(l)).collect();
+    assert!(totals[0] < totals[1] && totals[1] <= totals[2], "{totals:?}");
+}
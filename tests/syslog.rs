@@ -0,0 +1,40 @@
+#![cfg(feature = "syslog")]
+use std::net::UdpSocket;
+use std::time::Duration;
+
+use rustlog::{info_group, warn, Facility};
+
+#[test]
+fn syslog_sends_one_rfc5424_datagram_per_line() {
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    server.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    rustlog::set_level(rustlog::Level::Info);
+    rustlog::set_syslog(server.local_addr().unwrap(), Facility::Local0, "my app").unwrap();
+    rustlog::set_format(Box::new(
+        rustlog::SyslogFormat::new(Facility::Local0, "my app").with_hostname("box1"),
+    ));
+
+    info_group!("db", "connected to {}", "primary");
+    warn!("disk at {}%", 91);
+
+    let mut buf = [0u8; 2048];
+    let mut recv = || {
+        let n = server.recv(&mut buf).unwrap();
+        String::from_utf8(buf[..n].to_vec()).unwrap()
+    };
+    let first = recv();
+    let second = recv();
+    let pid = std::process::id();
+
+    // <16*8 + 6>1 TIMESTAMP HOST APP PROCID MSGID - MSG
+    let parts: Vec<&str> = first.splitn(7, ' ').collect();
+    assert_eq!(parts[0], "<134>1");
+    assert_eq!(parts[1].len(), "2024-01-01T00:00:00.000Z".len(), "{first}");
+    assert_eq!(parts[1].as_bytes()[10], b'T');
+    assert!(parts[1].ends_with('Z'));
+    assert_eq!(&parts[2..], ["box1", "myapp", &pid.to_string(), "db", "- connected to primary"]);
+    assert!(!first.ends_with('\n'));
+
+    assert!(second.starts_with("<132>1 "), "{second}");
+    assert!(second.ends_with(&format!(" box1 myapp {pid} - - disk at 91%")), "{second}");
+}
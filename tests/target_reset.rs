@@ -0,0 +1,40 @@
+use std::{fs, path::PathBuf};
+
+fn temp_log(tag: &str) -> PathBuf {
+    let mut p: PathBuf = std::env::temp_dir();
+    p.push(format!("rustlog_reset_{tag}_{}_{}.log",
+        std::process::id(),
+        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()));
+    p
+}
+
+#[test]
+fn set_target_and_set_file_replace_the_sink() {
+    let a = temp_log("a");
+    let b = temp_log("b");
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+
+    rustlog::set_file(&a).expect("set_file a");
+    rustlog::info!("first");
+    rustlog::set_file(&b).expect("set_file b");
+    rustlog::info!("second");
+
+    // switching away from the writer stops output to the file
+    rustlog::set_target(rustlog::Target::Stderr);
+    rustlog::info!("third");
+    rustlog::set_target(rustlog::Target::Writer);
+    rustlog::info!("fourth");
+
+    // resetting drops the writer and goes back to stderr
+    rustlog::reset_target();
+    rustlog::info!("fifth");
+
+    let sa = fs::read_to_string(&a).expect("read a");
+    let sb = fs::read_to_string(&b).expect("read b");
+    assert!(sa.contains("first") && !sa.contains("second"), "a:\n{sa}");
+    assert!(sb.contains("second") && sb.contains("fourth"), "b:\n{sb}");
+    assert!(!sb.contains("third") && !sb.contains("fifth"), "b:\n{sb}");
+    let _ = fs::remove_file(&a);
+    let _ = fs::remove_file(&b);
+}
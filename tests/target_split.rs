@@ -0,0 +1,38 @@
+use std::process::Command;
+
+const CHILD_ENV: &str = "RUSTLOG_SPLIT_CHILD";
+
+// Runs only inside the subprocess spawned by `split_routes_by_level`.
+#[test]
+fn split_child() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+    rustlog::set_show_time(false);
+    rustlog::set_show_thread_id(false);
+    rustlog::set_show_file_line(false);
+    rustlog::set_color_mode(rustlog::ColorMode::Never);
+    rustlog::set_split(rustlog::Level::Warn);
+    assert_eq!(rustlog::target(), rustlog::Target::Split(rustlog::Level::Warn));
+    rustlog::info!("normal output");
+    rustlog::warn!("careful");
+    rustlog::error!("broken");
+}
+
+#[test]
+fn split_routes_by_level() {
+    let out = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "split_child", "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let stderr = String::from_utf8(out.stderr).unwrap();
+    assert!(stdout.contains("INFO  normal output"), "{stdout}");
+    assert!(!stdout.contains("careful") && !stdout.contains("broken"), "{stdout}");
+    assert!(stderr.contains("WARN  careful\n"), "{stderr}");
+    assert!(stderr.contains("ERROR broken\n"), "{stderr}");
+    assert!(!stderr.contains("normal output"), "{stderr}");
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "thread-id")]
+mod helpers;
+use helpers::*;
+use rustlog::{info, ThreadLabel};
+
+#[test]
+fn named_thread_shows_its_name() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_show_thread_id(true);
+
+    let log_from = |name: Option<&str>| {
+        let b = name.map_or_else(std::thread::Builder::new, |n| {
+            std::thread::Builder::new().name(n.to_string())
+        });
+        b.spawn(|| info!("hello")).unwrap().join().unwrap();
+    };
+    log_from(Some("worker-2"));
+    rustlog::set_thread_label(ThreadLabel::Name);
+    log_from(Some("worker-2"));
+    rustlog::set_thread_label(ThreadLabel::NameOrId);
+    log_from(None);
+    rustlog::set_thread_label(ThreadLabel::Id);
+    rustlog::set_show_thread_id(false);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("[ThreadId("), "{:?}", lines[0]);
+    assert_eq!(lines[1], "INFO  [worker-2] hello");
+    assert!(lines[2].contains("[ThreadId("), "{:?}", lines[2]);
+}
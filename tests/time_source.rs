@@ -0,0 +1,37 @@
+#![cfg(feature = "timestamp")]
+mod helpers;
+use helpers::*;
+use rustlog::{info, TimeSource};
+
+fn uptime_of(line: &str) -> f64 {
+    let (secs, _) = line
+        .strip_prefix('[')
+        .and_then(|l| l.split_once("s]"))
+        .unwrap_or_else(|| panic!("no uptime prefix: {line:?}"));
+    secs.trim().parse().unwrap()
+}
+
+#[test]
+fn uptime_replaces_wall_clock() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_show_time(true);
+
+    rustlog::set_time_source(TimeSource::Uptime);
+    assert_eq!(rustlog::time_source(), TimeSource::Uptime);
+    info!("one");
+    std::thread::sleep(std::time::Duration::from_millis(5));
+    info!("two");
+    rustlog::set_time_source(TimeSource::Wall);
+    rustlog::set_show_time(false);
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 2);
+    for line in &lines {
+        assert!(line.contains("s] INFO  "), "{line:?}");
+        assert!(!line.contains('Z'), "{line:?}");
+    }
+    assert!(uptime_of(&lines[0]) <= uptime_of(&lines[1]), "{lines:?}");
+}
@@ -0,0 +1,50 @@
+mod helpers;
+use helpers::*;
+use rustlog::local::{Logger, TimerGuard as LocalTimerGuard};
+use rustlog::TimerGuard;
+use std::time::Duration;
+
+#[test]
+fn elapsed_grows_mid_scope_and_reset_restarts() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    {
+        let mut t = TimerGuard::new_at("work", file!(), line!());
+        std::thread::sleep(Duration::from_millis(2));
+        let a = t.elapsed();
+        std::thread::sleep(Duration::from_millis(2));
+        let b = t.elapsed();
+        assert!(a > Duration::ZERO && b > a, "{a:?} {b:?}");
+
+        std::thread::sleep(Duration::from_millis(20));
+        t.reset();
+        assert!(t.elapsed() < b + Duration::from_millis(20));
+    }
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains("[work] took "), "{:?}", lines[0]);
+}
+
+#[test]
+fn local_timer_reset_is_what_gets_logged() -> std::io::Result<()> {
+    let cap = rustlog::sink::CaptureSink::new(4);
+    let lg = Logger::builder()
+        .writer(Box::new(cap.clone()))
+        .set_show_time(false)
+        .set_show_file_line(false)
+        .set_show_thread_id(false)
+        .build()?;
+
+    let mut t = LocalTimerGuard::new_at_min(&lg, "slow", Duration::from_millis(50), file!(), 1);
+    std::thread::sleep(Duration::from_millis(60));
+    assert!(t.elapsed() >= Duration::from_millis(50));
+    t.reset();
+    // below `min` since the reset, so nothing is logged
+    drop(t);
+
+    assert!(cap.lines().is_empty(), "{:?}", cap.lines());
+    Ok(())
+}
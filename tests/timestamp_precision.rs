@@ -0,0 +1,83 @@
+#![cfg(all(feature = "timestamp", not(feature = "localtime")))]
+mod helpers;
+use helpers::*;
+use rustlog::{info, Precision};
+
+struct Fixed;
+impl rustlog::Clock for Fixed {
+    fn now_unix_nanos(&self) -> i128 {
+        1_705_276_800 * 1_000_000_000 + 123_456_789 // 2024-01-15 00:00:00.123456789 UTC
+    }
+}
+
+fn first_line_with(setup: impl FnOnce()) -> String {
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_clock(Box::new(Fixed));
+    rustlog::set_show_time(true);
+    setup();
+    info!("ts");
+    rustlog::set_timestamp_precision(Precision::Millis);
+    rustlog::set_timestamp_z_suffix(true);
+    rustlog::set_clock(Box::new(rustlog::SystemClock));
+    lines_from(&buf).remove(0)
+}
+
+#[test]
+fn micros_precision() {
+    let _g = test_lock().lock().unwrap();
+    let line = first_line_with(|| rustlog::set_timestamp_precision(Precision::Micros));
+    assert_eq!(line, "2024-01-15 00:00:00.123456Z INFO  ts");
+}
+
+#[test]
+fn secs_and_nanos_precision() {
+    let _g = test_lock().lock().unwrap();
+    let line = first_line_with(|| rustlog::set_timestamp_precision(Precision::Secs));
+    assert_eq!(line, "2024-01-15 00:00:00Z INFO  ts");
+    let line = first_line_with(|| rustlog::set_timestamp_precision(Precision::Nanos));
+    assert_eq!(line, "2024-01-15 00:00:00.123456789Z INFO  ts");
+}
+
+#[test]
+fn without_z_suffix() {
+    let _g = test_lock().lock().unwrap();
+    let line = first_line_with(|| rustlog::set_timestamp_z_suffix(false));
+    assert_eq!(line, "2024-01-15 00:00:00.123 INFO  ts");
+    assert!(rustlog::timestamp_z_suffix());
+}
+
+#[test]
+fn precision_and_suffix_are_per_logger() {
+    use rustlog::local::{info as linfo, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let _g = test_lock().lock().unwrap();
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(true)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_timestamp_precision(Precision::Secs)
+        .set_timestamp_z_suffix(false)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_timestamp_precision(Precision::Nanos);
+    rustlog::set_clock(Box::new(Fixed));
+
+    linfo!(&lg, "parent");
+    linfo!(&child, "child");
+
+    rustlog::set_clock(Box::new(rustlog::SystemClock));
+    assert_eq!(rustlog::timestamp_precision(), Precision::Millis);
+    assert_eq!(
+        cap.lines(),
+        [
+            "2024-01-15 00:00:00 INFO  parent",
+            "2024-01-15 00:00:00.123456789 INFO  child"
+        ]
+    );
+}
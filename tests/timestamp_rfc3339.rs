@@ -0,0 +1,78 @@
+#![cfg(feature = "timestamp")]
+mod helpers;
+use helpers::*;
+use rustlog::{info, TimestampFormat};
+
+fn rfc3339_line() -> String {
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_show_time(true);
+    rustlog::set_timestamp_format(TimestampFormat::Rfc3339);
+    info!("ts");
+    rustlog::set_timestamp_format(TimestampFormat::SpaceSeparated);
+    lines_from(&buf).remove(0)
+}
+
+#[test]
+#[cfg(not(feature = "localtime"))]
+fn utc_rfc3339_shape() {
+    struct Fixed;
+    impl rustlog::Clock for Fixed {
+        fn now_unix_nanos(&self) -> i128 {
+            1_705_320_000 * 1_000_000_000 // 2024-01-15 12:00:00 UTC
+        }
+    }
+
+    let _g = test_lock().lock().unwrap();
+    rustlog::set_clock(Box::new(Fixed));
+    let line = rfc3339_line();
+    rustlog::set_timestamp_z_suffix(false);
+    let no_z = rfc3339_line();
+    rustlog::set_timestamp_z_suffix(true);
+    rustlog::set_clock(Box::new(rustlog::SystemClock));
+
+    assert_eq!(line, "2024-01-15T12:00:00.000Z INFO  ts");
+    assert_eq!(no_z, "2024-01-15T12:00:00.000+00:00 INFO  ts");
+}
+
+#[test]
+#[cfg(feature = "localtime")]
+fn localtime_rfc3339_has_numeric_offset() {
+    let _g = test_lock().lock().unwrap();
+    let line = rfc3339_line();
+    let ts = line.split(' ').next().unwrap();
+
+    // `2024-01-15T12:00:00.000+02:00`
+    assert_eq!(ts.len(), 29, "{line:?}");
+    assert_eq!(&ts[10..11], "T", "{line:?}");
+    let offset = &ts[23..];
+    assert!(offset.starts_with(['+', '-']), "{line:?}");
+    assert_eq!(&offset[3..4], ":", "{line:?}");
+    assert!(!ts.contains('Z'), "{line:?}");
+}
+
+#[test]
+fn format_is_per_logger() {
+    use rustlog::local::{info as linfo, Logger};
+    use rustlog::sink::CaptureSink;
+
+    let cap = CaptureSink::new(4);
+    let lg = Logger::builder()
+        .set_writer(Box::new(cap.clone()))
+        .set_show_time(true)
+        .set_show_thread_id(false)
+        .set_show_file_line(false)
+        .set_timestamp_format(TimestampFormat::Rfc3339)
+        .build()
+        .unwrap();
+    let child = lg.child();
+    child.set_timestamp_format(TimestampFormat::SpaceSeparated);
+
+    linfo!(&lg, "parent");
+    linfo!(&child, "child");
+
+    let lines = cap.lines();
+    assert_eq!(&lines[0][10..11], "T", "{lines:?}");
+    assert_eq!(&lines[1][10..11], " ", "{lines:?}");
+}
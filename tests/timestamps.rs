@@ -44,3 +44,26 @@ fn localtime_timestamp_prefix() {
     let line = s.lines().next().unwrap_or("");
     assert!(!line.contains("Z "), "localtime should not include 'Z ': {line}");
 }
+
+#[test]
+#[cfg(all(feature = "timestamp", not(feature = "localtime")))]
+fn fixed_clock_exact_prefix() {
+    struct Fixed;
+    impl rustlog::Clock for Fixed {
+        fn now_unix_nanos(&self) -> i128 {
+            1_705_276_800 * 1_000_000_000 // 2024-01-15 00:00:00 UTC
+        }
+    }
+
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+
+    rustlog::set_clock(Box::new(Fixed));
+    rustlog::set_show_time(true);
+    info!("ts");
+    rustlog::set_clock(Box::new(rustlog::SystemClock));
+
+    let s = String::from_utf8(buf.lock().unwrap().clone()).unwrap();
+    assert!(s.starts_with("2024-01-15 00:00:00.000Z INFO "), "{s}");
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "tracing-compat")]
+mod helpers;
+use helpers::*;
+use tracing_subscriber::layer::SubscriberExt;
+
+#[test]
+fn tracing_events_render_as_rustlog_lines() {
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_level(rustlog::Level::Info);
+
+    let subscriber = tracing_subscriber::registry().with(rustlog::tracing_layer());
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!(target: "db", user = "ann", id = 7, "logged in as {}", "admin");
+        tracing::debug!(target: "db", "below the level");
+        tracing::warn!(target: "net", retry = true, "slow");
+    });
+
+    let lines = lines_from(&buf);
+    assert_eq!(
+        lines,
+        ["INFO  [db] logged in as admin user=ann id=7", "WARN  [net] slow retry=true"]
+    );
+}
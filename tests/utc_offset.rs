@@ -0,0 +1,31 @@
+#![cfg(all(feature = "timestamp", feature = "localtime"))]
+mod helpers;
+use helpers::*;
+use rustlog::info;
+
+#[test]
+fn explicit_offset_shifts_the_hour() {
+    struct Fixed;
+    impl rustlog::Clock for Fixed {
+        fn now_unix_nanos(&self) -> i128 {
+            1_705_320_000 * 1_000_000_000 // 2024-01-15 12:00:00 UTC
+        }
+    }
+
+    let _g = test_lock().lock().unwrap();
+    let (buf, _guard) = attach_mem_sink();
+    reset_runtime();
+    rustlog::set_show_file_line(false);
+    rustlog::set_clock(Box::new(Fixed));
+    rustlog::set_show_time(true);
+
+    rustlog::set_utc_offset(2 * 3600);
+    info!("plus two");
+    rustlog::set_utc_offset(-(5 * 3600 + 30 * 60));
+    info!("minus five thirty");
+    rustlog::set_clock(Box::new(rustlog::SystemClock));
+
+    let lines = lines_from(&buf);
+    assert_eq!(lines[0], "2024-01-15 14:00:00.000 INFO  plus two");
+    assert_eq!(lines[1], "2024-01-15 06:30:00.000 INFO  minus five thirty");
+}
@@ -0,0 +1,40 @@
+mod helpers;
+use helpers::*;
+use rustlog::Level;
+
+#[test]
+fn verbosity_counts_map_to_levels() {
+    let _g = test_lock().lock().unwrap();
+    let want = [
+        (0, Level::Warn),
+        (1, Level::Info),
+        (2, Level::Debug),
+        (3, Level::Trace),
+        (4, Level::Trace),
+        (u8::MAX, Level::Trace),
+    ];
+    for (v, l) in want {
+        assert_eq!(rustlog::set_level_from_verbosity(v), l, "-v x{v}");
+        assert_eq!(rustlog::level(), l);
+    }
+    reset_runtime();
+}
+
+#[test]
+fn quiet_counts_map_to_levels() {
+    let _g = test_lock().lock().unwrap();
+    let want = [
+        (0, Level::Warn),
+        (1, Level::Error),
+        (2, Level::Fatal),
+        (u8::MAX, Level::Fatal),
+    ];
+    for (q, l) in want {
+        assert_eq!(rustlog::set_level_from_quiet(q), l, "-q x{q}");
+        assert_eq!(rustlog::level(), l);
+    }
+    let lg = rustlog::local::Logger::default();
+    assert_eq!(lg.set_level_from_verbosity(2), Level::Debug);
+    assert_eq!(lg.level(), Level::Debug);
+    reset_runtime();
+}